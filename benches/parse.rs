@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate criterion;
+extern crate sshkeys;
+
+use criterion::{Criterion, Throughput};
+use std::io::{BufRead, BufReader};
+
+// Number of keys in the generated key set
+const KEY_COUNT: usize = 1000;
+
+// Builds a key set in the `authorized_keys` format from the test keys
+fn key_set() -> String {
+    let keys = [
+        include_str!("../tests/test-keys/id_rsa_2048.pub"),
+        include_str!("../tests/test-keys/id_ecdsa_256.pub"),
+        include_str!("../tests/test-keys/id_ed25519.pub"),
+    ];
+
+    keys.iter()
+        .cycle()
+        .take(KEY_COUNT)
+        .map(|key| key.trim())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Parsing through `PublicKey::iter_from_reader` reuses the line and decode buffers,
+// while reading the lines from a reader and parsing them with `PublicKey::from_string`
+// allocates a new line and decode buffer for every key. Parsing the lines of a string
+// which is already in memory only needs the decode buffer.
+fn parse(c: &mut Criterion) {
+    let data = key_set();
+
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Elements(KEY_COUNT as u64));
+
+    group.bench_function("from_string", |b| {
+        b.iter(|| {
+            for line in data.lines() {
+                sshkeys::PublicKey::from_string(line).unwrap();
+            }
+        })
+    });
+
+    group.bench_function("from_string_reader", |b| {
+        b.iter(|| {
+            for line in BufReader::new(data.as_bytes()).lines() {
+                sshkeys::PublicKey::from_string(&line.unwrap()).unwrap();
+            }
+        })
+    });
+
+    group.bench_function("iter_from_reader", |b| {
+        b.iter(|| {
+            for key in sshkeys::PublicKey::iter_from_reader(data.as_bytes()) {
+                key.unwrap();
+            }
+        })
+    });
+
+    group.bench_function("peek_key_type", |b| {
+        b.iter(|| {
+            for line in data.lines() {
+                sshkeys::PublicKey::peek_key_type(line).unwrap();
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
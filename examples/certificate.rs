@@ -10,11 +10,12 @@ fn main() {
     println!("Serial: {}", cert.serial);
     println!("Valid from {} to {}", cert.valid_after, cert.valid_before);
     println!("Principals:");
-    for p in cert.valid_principals {
+    for p in &cert.valid_principals {
         println!("\t{}", p);
     }
     println!("Critical Options:");
-    for (name, value) in cert.critical_options {
+    for name in cert.critical_options.keys() {
+        let value = cert.critical_option(name).unwrap().unwrap_or_default();
         println!("\t{} {}", name, value);
     }
     println!("Extensions:");
@@ -0,0 +1,61 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::error::{Error, ErrorKind, Result};
+use super::pubkey::PublicKey;
+use super::reader::Reader;
+
+// The message number of the agent reply to a `SSH2_AGENTC_REQUEST_IDENTITIES` request.
+const SSH2_AGENT_IDENTITIES_ANSWER: u8 = 12;
+
+/// Reads the identities from the payload of an ssh-agent `SSH2_AGENT_IDENTITIES_ANSWER`
+/// message, which is the reply to a `SSH2_AGENTC_REQUEST_IDENTITIES` request.
+///
+/// The payload starts with the message number, followed by the number of identities
+/// and the key blob and comment of each identity. The length of the message, which
+/// precedes the payload on the wire, must not be part of the payload.
+/// Please refer to [draft-miller-ssh-agent], section 4.4 for more details.
+///
+/// [draft-miller-ssh-agent]: https://tools.ietf.org/html/draft-miller-ssh-agent-04
+///
+/// # Example
+///
+/// ```rust
+/// # use sshkeys;
+/// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd").unwrap();
+///
+/// let mut writer = sshkeys::Writer::new();
+/// writer.write_u8(12);
+/// writer.write_u32(1);
+/// writer.write_bytes(&key.encode());
+/// writer.write_string("me@home");
+/// let payload = writer.into_bytes();
+///
+/// let identities = sshkeys::parse_agent_identities(&payload).unwrap();
+/// assert_eq!(identities, vec![(key, "me@home".to_string())]);
+/// ```
+pub fn parse_agent_identities(payload: &[u8]) -> Result<Vec<(PublicKey, String)>> {
+    let mut reader = Reader::new(payload);
+    if reader.read_u8()? != SSH2_AGENT_IDENTITIES_ANSWER {
+        return Err(Error::with_kind(ErrorKind::InvalidFormat));
+    }
+
+    // The number of identities is not trusted for allocating the result,
+    // since each identity is read and bounds checked separately.
+    let count = reader.read_u32()?;
+    let mut identities = Vec::new();
+
+    for _ in 0..count {
+        let key = reader
+            .read_bytes()
+            .and_then(|v| PublicKey::from_bytes(&v))?;
+        let comment = reader.read_string()?;
+        identities.push((key, comment));
+    }
+
+    if reader.remaining() > 0 {
+        return Err(Error::with_kind(ErrorKind::TrailingData));
+    }
+
+    Ok(identities)
+}
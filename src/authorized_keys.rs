@@ -0,0 +1,204 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{BufRead, BufReader, Read};
+#[cfg(feature = "std")]
+use std::mem;
+
+use super::error::{Error, ErrorKind, Result};
+use super::keytype::KeyType;
+use super::pubkey::PublicKey;
+
+/// A type which represents an entry in an OpenSSH `authorized_keys` file.
+/// Please refer to the `AUTHORIZED_KEYS FILE FORMAT` section of sshd(8) for more details.
+#[derive(Debug)]
+pub struct AuthorizedKey {
+    /// The options preceding the key, if any, e.g. `no-pty,command="/usr/bin/true"`.
+    pub options: Option<String>,
+
+    /// The public key.
+    pub key: PublicKey,
+
+    /// Associated comment, if any.
+    pub comment: Option<String>,
+}
+
+impl AuthorizedKey {
+    /// Reads an `authorized_keys` entry from a given line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let entry = sshkeys::AuthorizedKey::from_string("no-pty,command=\"echo a,b\" ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert_eq!(entry.options, Some("no-pty,command=\"echo a,b\"".to_string()));
+    /// assert_eq!(entry.options_list(), vec!["no-pty", "command=\"echo a,b\""]);
+    /// assert_eq!(entry.comment, Some("me@home".to_string()));
+    /// ```
+    pub fn from_string(line: &str) -> Result<AuthorizedKey> {
+        let line = line.trim();
+
+        // A line starts either with the key type, or with the options
+        // followed by the key type.
+        let (first, rest) = split_options(line)?;
+        let (options, key_line) = match KeyType::from_token(first) {
+            Ok(_) => (None, line),
+            Err(_) => (Some(first.to_string()), rest.trim_start()),
+        };
+
+        let key = PublicKey::from_string(key_line)?;
+        let entry = AuthorizedKey {
+            options,
+            comment: key.comment.clone(),
+            key,
+        };
+
+        Ok(entry)
+    }
+
+    /// Returns the individual options of the entry.
+    ///
+    /// Commas within quoted option values are not treated as separators.
+    pub fn options_list(&self) -> Vec<&str> {
+        let options = match self.options {
+            Some(ref v) => v,
+            None => return Vec::new(),
+        };
+
+        let mut items = Vec::new();
+        let mut start = 0;
+        let mut quoted = false;
+        let mut escaped = false;
+
+        for (i, c) in options.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if quoted => escaped = true,
+                '"' => quoted = !quoted,
+                ',' if !quoted => {
+                    items.push(&options[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        items.push(&options[start..]);
+
+        items
+    }
+}
+
+/// Reads all entries from an OpenSSH `authorized_keys` file.
+///
+/// Blank lines and lines starting with `#` are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// # use sshkeys;
+/// # fn example() -> sshkeys::Result<()> {
+/// let file = std::fs::File::open("/path/to/authorized_keys")?;
+/// let entries = sshkeys::parse_authorized_keys(file)?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_authorized_keys<R: Read>(r: R) -> Result<Vec<AuthorizedKey>> {
+    let mut entries = Vec::new();
+
+    for line in BufReader::new(r).lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        entries.push(AuthorizedKey::from_string(trimmed)?);
+    }
+
+    Ok(entries)
+}
+
+/// Reads all entries from an OpenSSH `authorized_keys` file, collecting the errors
+/// of invalid lines instead of stopping at the first one.
+///
+/// Returns the successfully parsed entries along with the line numbers, starting at one,
+/// and the errors of the lines which could not be parsed, e.g. for reporting all invalid
+/// lines of a file at once. Blank lines and lines starting with `#` are skipped.
+/// An I/O error stops the reading and is reported for the line being read.
+///
+/// # Example
+///
+/// ```rust
+/// # use sshkeys;
+/// let contents = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home
+/// ssh-ed25519 invalid
+/// ";
+/// let (entries, errors) = sshkeys::parse_authorized_keys_lenient(contents.as_bytes());
+/// assert_eq!(entries.len(), 1);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].0, 2);
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_authorized_keys_lenient<R: Read>(r: R) -> (Vec<AuthorizedKey>, Vec<(usize, Error)>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    let mut reader = BufReader::new(r);
+    let mut buf = Vec::new();
+
+    for line_number in 1.. {
+        // Lines are read as bytes, so that a line which is not valid
+        // UTF-8 is reported without stopping the reading.
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => {
+                errors.push((line_number, Error::from(e)));
+                break;
+            }
+        }
+
+        let line = match String::from_utf8(mem::take(&mut buf)) {
+            Ok(v) => v,
+            Err(e) => {
+                errors.push((line_number, Error::from(e)));
+                continue;
+            }
+        };
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        match AuthorizedKey::from_string(trimmed) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => errors.push((line_number, e)),
+        }
+    }
+
+    (entries, errors)
+}
+
+// Splits a line at the first whitespace character which is not part
+// of a quoted string, e.g. `command="echo foo bar"`.
+fn split_options(line: &str) -> Result<(&str, &str)> {
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => return Ok(line.split_at(i)),
+            _ => {}
+        }
+    }
+
+    if quoted {
+        return Err(Error::with_kind(ErrorKind::InvalidFormat));
+    }
+
+    Ok((line, ""))
+}
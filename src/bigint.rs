@@ -0,0 +1,76 @@
+extern crate num_bigint;
+
+use self::num_bigint::BigUint;
+use super::error::{Error, ErrorKind, Result};
+use super::pubkey::{DsaPublicKey, RsaPublicKey};
+use super::reader::Reader;
+
+impl<'a> Reader<'a> {
+    /// Reads an `mpint` value from the wrapped byte sequence as an unsigned big integer.
+    ///
+    /// The leading zero byte, which marks a positive number according to RFC 4251, section 5,
+    /// is not part of the value. Negative numbers result in an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// # extern crate num_bigint;
+    /// let data = vec![0, 0, 0, 4, 0, 0x80, 0, 1];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// let value = reader.read_biguint().unwrap();
+    /// assert_eq!(value, num_bigint::BigUint::from(0x800001u32));
+    /// ```
+    pub fn read_biguint(&mut self) -> Result<BigUint> {
+        let bytes = self.read_bytes_ref()?;
+
+        // The most significant bit of the first byte is the sign bit
+        if bytes.first().unwrap_or(&0) & 0x80 != 0 {
+            return Err(Error::with_kind(ErrorKind::InvalidFormat));
+        }
+
+        Ok(BigUint::from_bytes_be(bytes))
+    }
+}
+
+impl RsaPublicKey {
+    /// Returns the modulus `n` of the key as an unsigned big integer.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_rsa(vec![1, 0, 1], vec![0x80, 0, 0, 1]);
+    /// if let sshkeys::PublicKeyKind::Rsa(ref k) = key.kind {
+    ///     assert_eq!(k.modulus_biguint().bits(), 32);
+    /// }
+    /// ```
+    pub fn modulus_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.n)
+    }
+
+    /// Returns the exponent `e` of the key as an unsigned big integer.
+    pub fn exponent_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.e)
+    }
+}
+
+impl DsaPublicKey {
+    /// Returns the prime `p` of the key as an unsigned big integer.
+    pub fn p_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.p)
+    }
+
+    /// Returns the subprime `q` of the key as an unsigned big integer.
+    pub fn q_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.q)
+    }
+
+    /// Returns the generator `g` of the key as an unsigned big integer.
+    pub fn g_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.g)
+    }
+
+    /// Returns the public value `y` of the key as an unsigned big integer.
+    pub fn y_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.y)
+    }
+}
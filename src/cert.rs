@@ -1,18 +1,31 @@
-use std::collections::HashMap;
-use std::fmt;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
 use std::fs::File;
-use std::io::Read;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+#[cfg(feature = "std")]
 use std::path::Path;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::error::{Error, ErrorKind, Result};
 use super::keytype::KeyType;
-use super::pubkey::PublicKey;
+use super::pubkey::{parse_comment, split_key_line, PublicKey};
+#[cfg(feature = "verify")]
+use super::pubkey::{PublicKeyKind, RsaHash};
 use super::reader::Reader;
+use super::signature::Signature;
+#[cfg(feature = "verify")]
+use super::signature::SignatureKind;
+use super::writer::Writer;
 
 use base64;
 
 /// Represents the different types a certificate can be.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CertType {
     /// Represents a user certificate.
     User,
@@ -64,16 +77,20 @@ pub struct Certificate {
 
     /// Critical options of the certificate. Generally used to
     /// control features which restrict access.
-    pub critical_options: HashMap<String, String>,
+    /// The values are the raw option data, use `Certificate::critical_option`
+    /// in order to get the value of an option as string.
+    pub critical_options: BTreeMap<String, Vec<u8>>,
 
     /// Certificate extensions. Extensions are usually used to
     /// enable features that grant access.
-    pub extensions: HashMap<String, String>,
+    /// The values are the raw extension data, which is usually empty.
+    pub extensions: BTreeMap<String, Vec<u8>>,
 
     /// The `reserved` field is currently unused and is ignored in this version of the protocol.
     pub reserved: Vec<u8>,
 
     /// Signature key contains the CA public key used to sign the certificate.
+    /// It can be compared against a list of trusted CA keys, e.g. using `PublicKey::matches`.
     pub signature_key: PublicKey,
 
     /// Signature of the certificate.
@@ -81,6 +98,90 @@ pub struct Certificate {
 
     /// Associated comment, if any.
     pub comment: Option<String>,
+
+    // The signed part of the certificate as it was read, i.e. all fields up to and
+    // including the CA public key, which the signature is verified against.
+    #[cfg_attr(not(feature = "verify"), allow(dead_code))]
+    pub(crate) signed_data: Vec<u8>,
+}
+
+impl fmt::Display for Certificate {
+    /// Formats the certificate the same way `ssh-keygen -L` does, e.g.
+    ///
+    /// ```text
+    ///         Type: ssh-ed25519-cert-v01@openssh.com user certificate
+    ///         Public key: ED25519-CERT SHA256:ppYFPx0k4Ogs230n6eX9vGPpnNsTB0LPrDWXh1YjClA
+    ///         Signing CA: RSA SHA256:8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s (using ssh-rsa)
+    ///         Key ID: "john.doe"
+    ///         Serial: 0
+    ///         Valid: from 2017-10-02T08:49:00 to 2018-10-01T08:50:41
+    ///         Principals:
+    ///                 root
+    ///         Critical Options: (none)
+    ///         Extensions:
+    ///                 permit-pty
+    /// ```
+    ///
+    /// Unlike `ssh-keygen`, which uses the local time zone, the validity times are in UTC.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "        Type: {} {}", self.key_type.name, self.cert_type)?;
+        writeln!(
+            f,
+            "        Public key: {} {}",
+            self.key_type.short_name,
+            self.key.fingerprint()
+        )?;
+        write!(
+            f,
+            "        Signing CA: {} {}",
+            self.signature_key.key_type.short_name,
+            self.signature_key.fingerprint()
+        )?;
+        match Signature::from_bytes(&self.signature) {
+            Ok(sig) => writeln!(f, " (using {})", sig.algorithm)?,
+            Err(_) => writeln!(f)?,
+        }
+        writeln!(f, "        Key ID: \"{}\"", self.key_id)?;
+        writeln!(f, "        Serial: {}", self.serial)?;
+        writeln!(f, "        Valid: {}", self.format_validity())?;
+
+        if self.valid_principals.is_empty() {
+            writeln!(f, "        Principals: (none)")?;
+        } else {
+            writeln!(f, "        Principals: ")?;
+            for p in &self.valid_principals {
+                writeln!(f, "                {}", p)?;
+            }
+        }
+
+        if self.critical_options.is_empty() {
+            writeln!(f, "        Critical Options: (none)")?;
+        } else {
+            writeln!(f, "        Critical Options: ")?;
+            for (name, data) in &self.critical_options {
+                match Reader::new(data).read_string() {
+                    Ok(value) => writeln!(f, "                {} {}", name, value)?,
+                    Err(_) => writeln!(
+                        f,
+                        "                {} UNKNOWN OPTION (len {})",
+                        name,
+                        data.len()
+                    )?,
+                }
+            }
+        }
+
+        if self.extensions.is_empty() {
+            write!(f, "        Extensions: (none)")
+        } else {
+            write!(f, "        Extensions: ")?;
+            for name in self.extensions.keys() {
+                write!(f, "\n                {}", name)?;
+            }
+
+            Ok(())
+        }
+    }
 }
 
 impl Certificate {
@@ -95,6 +196,7 @@ impl Certificate {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "std")]
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Certificate> {
         let mut contents = String::new();
         File::open(path)?.read_to_string(&mut contents)?;
@@ -114,33 +216,299 @@ impl Certificate {
     /// # }
     /// ```
     pub fn from_string(s: &str) -> Result<Certificate> {
-        let mut iter = s.split_whitespace();
-
-        let kt_name = iter
-            .next()
-            .ok_or(Error::with_kind(ErrorKind::InvalidFormat))?;
+        let (kt_name, data, rest) = split_key_line(s)?;
 
-        let kt = KeyType::from_name(&kt_name)?;
+        let kt = KeyType::from_token(kt_name)?;
         if !kt.is_cert {
             return Err(Error::with_kind(ErrorKind::NotCertificate));
         }
 
-        let data = iter
-            .next()
-            .ok_or(Error::with_kind(ErrorKind::InvalidFormat))?;
-
-        let comment = iter.next().map(|v| String::from(v));
-        let decoded = base64::decode(&data)?;
+        let decoded = base64::decode(data)?;
         let mut reader = Reader::new(&decoded);
 
         // Validate key types before reading the rest of the data
         let kt_from_reader = reader.read_string()?;
-        if kt_name != kt_from_reader {
+        if kt.name != kt_from_reader {
             return Err(Error::with_kind(ErrorKind::KeyTypeMismatch));
         }
 
+        let mut cert = Certificate::from_reader(kt, &mut reader)?;
+        cert.comment = parse_comment(rest);
+
+        Ok(cert)
+    }
+
+    /// Reads an OpenSSH certificate from a given byte sequence.
+    ///
+    /// The byte sequence is expected to be the base64 decoded body of the certificate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let data = std::fs::read("/path/to/id_ed25519-cert.bin")?;
+    /// let cert = sshkeys::Certificate::from_bytes(&data)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_bytes<T: ?Sized + AsRef<[u8]>>(data: &T) -> Result<Certificate> {
+        let mut reader = Reader::new(&data);
+        let kt_name = reader.read_string()?;
+
+        let kt = KeyType::from_exact_name(&kt_name)?;
+        if !kt.is_cert {
+            return Err(Error::with_kind(ErrorKind::NotCertificate));
+        }
+
+        Certificate::from_reader(kt, &mut reader)
+    }
+
+    /// Returns the type of the certificate, i.e. whether it is a user or a host certificate.
+    ///
+    /// Certificates with an unknown type are rejected when they are read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let cert = sshkeys::Certificate::from_path("/path/to/id_ed25519-cert.pub")?;
+    /// if cert.cert_type() == sshkeys::CertType::Host {
+    ///     println!("Host certificate for {:?}", cert.valid_principals);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cert_type(&self) -> CertType {
+        self.cert_type
+    }
+
+    /// Returns `true` if the certificate is valid at the given time, which is
+    /// represented as the number of seconds since the Unix epoch.
+    ///
+    /// The certificate is valid starting from `valid_after` up to, but not including,
+    /// `valid_before`, the same way OpenSSH checks the validity of certificates.
+    /// A `valid_before` value of `u64::MAX` means that the certificate never expires.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let cert = sshkeys::Certificate::from_path("/path/to/id_ed25519-cert.pub")?;
+    /// if !cert.is_valid_at(1506340920) {
+    ///     println!("Certificate is expired or not yet valid");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_valid_at(&self, unix_time: u64) -> bool {
+        if unix_time < self.valid_after {
+            return false;
+        }
+
+        self.valid_before == u64::MAX || unix_time < self.valid_before
+    }
+
+    /// Returns `true` if the certificate is valid at the current system time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let cert = sshkeys::Certificate::from_path("/path/to/id_ed25519-cert.pub")?;
+    /// if cert.is_valid_now() {
+    ///     println!("Certificate is valid");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn is_valid_now(&self) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        self.is_valid_at(now)
+    }
+
+    /// Returns the value of a critical option, e.g. `force-command` or `source-address`.
+    ///
+    /// The value of critical options is a `string`, which is embedded in the option data.
+    /// Returns `None` if the certificate does not have the critical option.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let cert = sshkeys::Certificate::from_path("/path/to/id_ed25519-cert.pub")?;
+    /// if let Some(command) = cert.critical_option("force-command")? {
+    ///     println!("Forced command: {}", command);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn critical_option(&self, name: &str) -> Result<Option<String>> {
+        option_string(&self.critical_options, name)
+    }
+
+    /// Returns the value of an extension as `string`.
+    ///
+    /// Most extensions, e.g. `permit-pty`, are flags and their value is the empty string.
+    /// Returns `None` if the certificate does not have the extension.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let cert = sshkeys::Certificate::from_path("/path/to/id_ed25519-cert.pub")?;
+    /// if cert.extension("permit-pty")?.is_some() {
+    ///     println!("PTY allocation is permitted");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extension(&self, name: &str) -> Result<Option<String>> {
+        option_string(&self.extensions, name)
+    }
+
+    /// Returns `true` if the given principal is allowed by the certificate.
+    ///
+    /// The principals of user certificates are usernames, while the principals of
+    /// host certificates are hostnames. A certificate without any principals is
+    /// valid for any principal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let cert = sshkeys::Certificate::from_path("/path/to/id_ed25519-cert.pub")?;
+    /// if cert.is_principal_allowed("root") {
+    ///     println!("Certificate is valid for root");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_principal_allowed(&self, principal: &str) -> bool {
+        self.valid_principals.is_empty() || self.valid_principals.iter().any(|p| p == principal)
+    }
+
+    /// Verifies the signature of the certificate using the CA public key of the certificate.
+    ///
+    /// The signature is verified against the signed part of the certificate as it was read,
+    /// i.e. all fields up to and including the CA public key, the same way as OpenSSH does.
+    /// Modifying the fields of the certificate therefore does not affect the result.
+    /// ED25519, RSA, ECDSA and DSA signatures are supported.
+    ///
+    /// Returns `Ok(false)` if the signature does not match, and an error if the signature
+    /// is malformed or was made using an unsupported or mismatched algorithm.
+    ///
+    /// This method is only available with the `verify` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let cert = sshkeys::Certificate::from_path("/path/to/id_ed25519-cert.pub")?;
+    /// if cert.verify()? {
+    ///     println!("Certificate is signed by {}", cert.signature_key.fingerprint());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "verify")]
+    pub fn verify(&self) -> Result<bool> {
+        let signature = Signature::from_bytes(&self.signature)?;
+        let data = &self.signed_data;
+
+        match (&self.signature_key.kind, &signature.kind) {
+            (PublicKeyKind::Rsa(k), SignatureKind::Rsa(blob)) => {
+                let hash = match signature.algorithm.as_str() {
+                    "ssh-rsa" => RsaHash::Sha1,
+                    "rsa-sha2-256" => RsaHash::Sha256,
+                    "rsa-sha2-512" => RsaHash::Sha512,
+                    _ => {
+                        return Err(Error::with_kind(ErrorKind::UnknownSignatureType(
+                            signature.algorithm.clone(),
+                        )))
+                    }
+                };
+
+                k.verify(data, blob, hash)
+            }
+            (PublicKeyKind::Ecdsa(k), SignatureKind::Ecdsa(sig)) => {
+                // The signature algorithm depends on the curve of the key
+                if signature.algorithm != self.signature_key.key_type.name {
+                    return Err(Error::with_kind(ErrorKind::KeyTypeMismatch));
+                }
+
+                let mut w = Writer::new();
+                w.write_mpint(&sig.r);
+                w.write_mpint(&sig.s);
+
+                k.verify(data, &w.into_bytes())
+            }
+            (PublicKeyKind::Ed25519(k), SignatureKind::Ed25519(blob)) => k.verify(data, blob),
+            (PublicKeyKind::Dsa(k), SignatureKind::Dsa(blob)) => k.verify(data, blob),
+            (PublicKeyKind::Rsa(_), _)
+            | (PublicKeyKind::Dsa(_), _)
+            | (PublicKeyKind::Ecdsa(_), _)
+            | (PublicKeyKind::Ed25519(_), _) => Err(Error::with_kind(ErrorKind::KeyTypeMismatch)),
+            _ => Err(Error::with_kind(ErrorKind::UnknownSignatureType(
+                signature.algorithm.clone(),
+            ))),
+        }
+    }
+
+    // Formats the validity period of the certificate the same way OpenSSH does.
+    fn format_validity(&self) -> String {
+        match (self.valid_after, self.valid_before) {
+            (0, u64::MAX) => "forever".to_string(),
+            (0, before) => format!("before {}", format_time(before)),
+            (after, u64::MAX) => format!("after {}", format_time(after)),
+            (after, before) => format!("from {} to {}", format_time(after), format_time(before)),
+        }
+    }
+
+    // Encodes the signed part of the certificate, i.e. all fields up to and
+    // including the CA public key, in the same order as they were read.
+    fn encode_signed_data(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        w.write_string(self.key_type.name);
+        w.write_bytes(&self.nonce);
+        self.key.write_key_data(&mut w);
+        w.write_u64(self.serial);
+        w.write_u32(match self.cert_type {
+            CertType::User => 1,
+            CertType::Host => 2,
+        });
+        w.write_string(&self.key_id);
+        w.write_nested(|w| {
+            for p in &self.valid_principals {
+                w.write_string(p);
+            }
+        });
+        w.write_u64(self.valid_after);
+        w.write_u64(self.valid_before);
+        w.write_nested(|w| write_options(w, &self.critical_options));
+        w.write_nested(|w| write_options(w, &self.extensions));
+        w.write_bytes(&self.reserved);
+        w.write_bytes(&self.signature_key.encode());
+
+        w.into_bytes()
+    }
+
+    // Reads the certificate fields which follow the key type from an existing reader.
+    fn from_reader(kt: KeyType, reader: &mut Reader) -> Result<Certificate> {
         let nonce = reader.read_bytes()?;
-        let key = PublicKey::from_reader(&kt_name, &mut reader)?;
+        let key = PublicKey::from_reader(kt.name, reader)?;
         let serial = reader.read_u64()?;
 
         let cert_type = match reader.read_u32()? {
@@ -159,71 +527,326 @@ impl Certificate {
         let signature_key = reader
             .read_bytes()
             .and_then(|v| PublicKey::from_bytes(&v))?;
+        let signed_data = reader.consumed().to_vec();
         let signature = reader.read_bytes()?;
 
         let cert = Certificate {
             key_type: kt,
-            nonce: nonce,
-            key: key,
-            serial: serial,
-            cert_type: cert_type,
-            key_id: key_id,
+            nonce,
+            key,
+            serial,
+            cert_type,
+            key_id,
             valid_principals: principals,
-            valid_after: valid_after,
-            valid_before: valid_before,
-            critical_options: critical_options,
-            extensions: extensions,
-            reserved: reserved,
-            signature_key: signature_key,
-            signature: signature,
-            comment: comment,
+            valid_after,
+            valid_before,
+            critical_options,
+            extensions,
+            reserved,
+            signature_key,
+            signature,
+            comment: None,
+            signed_data,
         };
 
         Ok(cert)
     }
 }
 
+/// A builder for encoding OpenSSH certificates, which can then be signed by a CA.
+///
+/// The builder produces the to-be-signed byte sequence of the certificate, i.e. all fields
+/// of the certificate up to and including the CA public key. The certificate is complete
+/// once the signature of these bytes is appended as a `string` value.
+///
+/// # Example
+///
+/// ```rust
+/// # use sshkeys;
+/// # fn sign(data: &[u8]) -> Vec<u8> { vec![0; 64] }
+/// # fn example() -> sshkeys::Result<()> {
+/// let key = sshkeys::PublicKey::from_path("/path/to/id_ed25519.pub")?;
+/// let ca = sshkeys::PublicKey::from_path("/path/to/ca.pub")?;
+///
+/// let data = sshkeys::CertificateBuilder::new(key, sshkeys::CertType::User, ca)
+///     .key_id("john.doe")
+///     .principal("root")
+///     .valid_before(1536824561)
+///     .extension("permit-pty")
+///     .build()?;
+///
+/// let mut writer = sshkeys::Writer::new();
+/// writer.write_bytes(&sign(&data));
+///
+/// let mut encoded = data;
+/// encoded.extend_from_slice(&writer.into_bytes());
+/// let cert = sshkeys::Certificate::from_bytes(&encoded)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CertificateBuilder {
+    key: PublicKey,
+    cert_type: CertType,
+    signature_key: PublicKey,
+    nonce: Option<Vec<u8>>,
+    serial: u64,
+    key_id: String,
+    principals: Vec<String>,
+    valid_after: u64,
+    valid_before: u64,
+    critical_options: BTreeMap<String, Vec<u8>>,
+    extensions: BTreeMap<String, Vec<u8>>,
+}
+
+// The size of the random nonce generated for new certificates.
+#[cfg(feature = "std")]
+const NONCE_SIZE: usize = 32;
+
+impl CertificateBuilder {
+    /// Creates a new `CertificateBuilder` for certifying the given public key with the
+    /// given CA public key.
+    ///
+    /// The certificate is valid forever and for all principals, unless the validity
+    /// window or the principals are set.
+    pub fn new(
+        key: PublicKey,
+        cert_type: CertType,
+        signature_key: PublicKey,
+    ) -> CertificateBuilder {
+        CertificateBuilder {
+            key,
+            cert_type,
+            signature_key,
+            nonce: None,
+            serial: 0,
+            key_id: String::new(),
+            principals: Vec::new(),
+            valid_after: 0,
+            valid_before: u64::MAX,
+            critical_options: BTreeMap::new(),
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the nonce of the certificate.
+    ///
+    /// With the `std` feature enabled a random nonce is generated if none is set,
+    /// otherwise the nonce must be set before building the certificate.
+    pub fn nonce(mut self, nonce: Vec<u8>) -> CertificateBuilder {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the serial number of the certificate.
+    pub fn serial(mut self, serial: u64) -> CertificateBuilder {
+        self.serial = serial;
+        self
+    }
+
+    /// Sets the key identity of the certificate.
+    pub fn key_id(mut self, key_id: &str) -> CertificateBuilder {
+        self.key_id = key_id.to_string();
+        self
+    }
+
+    /// Adds a principal to the list of valid principals of the certificate.
+    pub fn principal(mut self, principal: &str) -> CertificateBuilder {
+        self.principals.push(principal.to_string());
+        self
+    }
+
+    /// Sets the time after which the certificate is valid.
+    pub fn valid_after(mut self, valid_after: u64) -> CertificateBuilder {
+        self.valid_after = valid_after;
+        self
+    }
+
+    /// Sets the time before which the certificate is valid.
+    pub fn valid_before(mut self, valid_before: u64) -> CertificateBuilder {
+        self.valid_before = valid_before;
+        self
+    }
+
+    /// Adds a critical option with a `string` value, e.g. `force-command`.
+    pub fn critical_option(mut self, name: &str, value: &str) -> CertificateBuilder {
+        let mut w = Writer::new();
+        w.write_string(value);
+        self.critical_options
+            .insert(name.to_string(), w.into_bytes());
+        self
+    }
+
+    /// Adds a flag extension, e.g. `permit-pty`.
+    pub fn extension(mut self, name: &str) -> CertificateBuilder {
+        self.extensions.insert(name.to_string(), Vec::new());
+        self
+    }
+
+    /// Encodes the to-be-signed byte sequence of the certificate.
+    ///
+    /// The critical options and extensions are written in lexical order, as
+    /// required by OpenSSH. Returns an `UnsupportedKeyType` error if there is no
+    /// OpenSSH certificate type for the key, e.g. for ED448 keys or for keys which
+    /// were read from a certificate.
+    pub fn build(self) -> Result<Vec<u8>> {
+        let kt = KeyType::from_name(cert_key_type(&self.key.key_type)?)?;
+
+        let nonce = match self.nonce {
+            Some(v) => v,
+            None => random_nonce()?,
+        };
+
+        let cert = Certificate {
+            key_type: kt,
+            nonce,
+            key: self.key,
+            serial: self.serial,
+            cert_type: self.cert_type,
+            key_id: self.key_id,
+            valid_principals: self.principals,
+            valid_after: self.valid_after,
+            valid_before: self.valid_before,
+            critical_options: self.critical_options,
+            extensions: self.extensions,
+            reserved: Vec::new(),
+            signature_key: self.signature_key,
+            signature: Vec::new(),
+            comment: None,
+            signed_data: Vec::new(),
+        };
+
+        Ok(cert.encode_signed_data())
+    }
+}
+
+// Returns the name of the certificate key type for certifying a key of the given plain
+// key type.
+fn cert_key_type(kt: &KeyType) -> Result<&'static str> {
+    let name = match kt.name {
+        "ssh-rsa" => "ssh-rsa-cert-v01@openssh.com",
+        "ssh-dss" => "ssh-dss-cert-v01@openssh.com",
+        "ecdsa-sha2-nistp256" => "ecdsa-sha2-nistp256-cert-v01@openssh.com",
+        "ecdsa-sha2-nistp384" => "ecdsa-sha2-nistp384-cert-v01@openssh.com",
+        "ecdsa-sha2-nistp521" => "ecdsa-sha2-nistp521-cert-v01@openssh.com",
+        "ssh-ed25519" => "ssh-ed25519-cert-v01@openssh.com",
+        "sk-ssh-ed25519@openssh.com" => "sk-ssh-ed25519-cert-v01@openssh.com",
+        "sk-ecdsa-sha2-nistp256@openssh.com" => "sk-ecdsa-sha2-nistp256-cert-v01@openssh.com",
+        _ => {
+            return Err(Error::with_kind(ErrorKind::UnsupportedKeyType(
+                kt.name.to_string(),
+            )))
+        }
+    };
+
+    Ok(name)
+}
+
+// Formats a time, represented as the number of seconds since the Unix epoch,
+// as an ISO 8601 date and time in UTC, e.g. `2017-10-02T08:49:00`.
+fn format_time(unix_time: u64) -> String {
+    let (days, secs) = (unix_time / 86400, unix_time % 86400);
+
+    // Converts the number of days since the epoch to a civil date, as described in
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+// Generates a random nonce for a new certificate.
+#[cfg(feature = "std")]
+fn random_nonce() -> Result<Vec<u8>> {
+    let mut nonce = vec![0; NONCE_SIZE];
+    if let Err(e) = getrandom::getrandom(&mut nonce) {
+        return Err(io::Error::from(e).into());
+    }
+
+    Ok(nonce)
+}
+
+// Without the `std` feature no source of randomness is available.
+#[cfg(not(feature = "std"))]
+fn random_nonce() -> Result<Vec<u8>> {
+    Err(Error::with_kind(ErrorKind::MissingNonce))
+}
+
+// Writes the `option` values of a certificate, which are already
+// ordered lexically by name, since they are kept in a `BTreeMap`.
+fn write_options(w: &mut Writer, options: &BTreeMap<String, Vec<u8>>) {
+    for (name, data) in options {
+        w.write_string(name);
+        w.write_bytes(data);
+    }
+}
+
 // Reads `option` values from a byte sequence.
 // The `option` values are used to represent the `critical options` and
 // `extensions` in an OpenSSH certificate key, which are represented as tuples
 // containing the `name` and `data` values of type `string`.
-// Some `options` are `flags` only (e.g. the certificate extensions) and the
-// associated value with them is the empty string (""), while others are `string`
-// options and have an associated value, which is a `string`.
-// The `critical options` of a certificate are always `string` options, since they
-// have an associated `string` value, which is embedded in a separate buffer, so
-// in order to extract the associated value we need to read the buffer first and then
-// read the `string` value itself.
-fn read_options(buf: &[u8]) -> Result<HashMap<String, String>> {
+// The `data` of each option is kept as is, since its contents depend on the option.
+// According to PROTOCOL.certkeys the options must be lexically ordered by name,
+// so options which are out of order or appear more than once are rejected.
+fn read_options(buf: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
     let mut reader = Reader::new(&buf);
-    let mut options = HashMap::new();
+    let mut options = BTreeMap::new();
 
     // Use a `Reader` and loop until EOF is reached, so that we can
     // read all options from the provided byte slice.
-    loop {
-        let name = match reader.read_string() {
-            Ok(v) => v,
-            Err(e) => match e.kind {
-                ErrorKind::UnexpectedEof => break,
-                _ => return Err(e),
-            },
-        };
+    while reader.remaining() > 0 {
+        let name = reader.read_string()?;
+        let data = reader.read_bytes()?;
 
-        // If we have a `string` option extract the value from the buffer,
-        // otherwise we have a `flag` option which is the `empty` string.
-        let value_buf = reader.read_bytes()?;
-        let value = if value_buf.len() > 0 {
-            Reader::new(&value_buf).read_string()?
-        } else {
-            "".to_string()
-        };
+        if options.keys().next_back().is_some_and(|last| *last >= name) {
+            return Err(Error::with_kind(ErrorKind::InvalidFormat));
+        }
 
-        options.insert(name, value);
+        options.insert(name, data);
     }
 
     Ok(options)
 }
 
+// Returns the value of an option as `string`.
+// Some `options` are `flags` only (e.g. the certificate extensions) and the
+// associated data is empty, in which case the value is the empty string (""),
+// while others are `string` options and have an associated `string` value,
+// which is embedded in the option data.
+fn option_string(options: &BTreeMap<String, Vec<u8>>, name: &str) -> Result<Option<String>> {
+    let data = match options.get(name) {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    if data.is_empty() {
+        return Ok(Some(String::new()));
+    }
+
+    let mut reader = Reader::new(data);
+    let value = reader.read_string()?;
+    if reader.remaining() > 0 {
+        return Err(Error::with_kind(ErrorKind::InvalidFormat));
+    }
+
+    Ok(Some(value))
+}
+
 // Reads the `valid principals` field of a certificate key.
 // The `valid principals` are represented as a sequence of `string` values
 // embedded in a buffer.
@@ -233,16 +856,8 @@ fn read_principals(buf: &[u8]) -> Result<Vec<String>> {
     let mut reader = Reader::new(&buf);
     let mut items = Vec::new();
 
-    loop {
-        let principal = match reader.read_string() {
-            Ok(v) => v,
-            Err(e) => match e.kind {
-                ErrorKind::UnexpectedEof => break,
-                _ => return Err(e),
-            },
-        };
-
-        items.push(principal);
+    while reader.remaining() > 0 {
+        items.push(reader.read_string()?);
     }
 
     Ok(items)
@@ -1,5 +1,9 @@
+use alloc::string::{self, String};
+use core::{fmt, result};
+#[cfg(feature = "std")]
 use std::error::Error as StdError;
-use std::{fmt, io, result, string};
+#[cfg(feature = "std")]
+use std::io;
 
 use base64;
 
@@ -12,28 +16,90 @@ pub struct Error {
 
 impl Error {
     pub(crate) fn with_kind(kind: ErrorKind) -> Error {
-        Error { kind: kind }
+        Error { kind }
+    }
+
+    /// Returns `true` if the error was caused by an input, which is either blank
+    /// or contains only a `#` comment, and thus does not contain a key.
+    ///
+    /// This allows callers reading a file line by line to skip such lines,
+    /// while still treating malformed lines as errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let err = sshkeys::PublicKey::from_string("# a comment").unwrap_err();
+    /// assert!(err.is_empty_input());
+    ///
+    /// let err = sshkeys::PublicKey::from_string("ssh-rsa").unwrap_err();
+    /// assert!(!err.is_empty_input());
+    /// ```
+    pub fn is_empty_input(&self) -> bool {
+        matches!(self.kind, ErrorKind::EmptyInput)
+    }
+
+    /// Returns the offset at which the end of the input was reached unexpectedly,
+    /// and the number of bytes which were needed at that offset, if the error was
+    /// caused by truncated input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 13, 97, 32, 116];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// let err = reader.read_string().unwrap_err();
+    /// assert_eq!(err.eof_offset(), Some((4, 13)));
+    ///
+    /// let err = sshkeys::PublicKey::from_string("ssh-rsa").unwrap_err();
+    /// assert_eq!(err.eof_offset(), None);
+    /// ```
+    pub fn eof_offset(&self) -> Option<(usize, usize)> {
+        match self.kind {
+            ErrorKind::UnexpectedEof { offset, needed } => Some((offset, needed)),
+            _ => None,
+        }
     }
 }
 
 /// A type to represent the different kinds of errors.
 #[derive(Debug)]
 pub(crate) enum ErrorKind {
+    #[cfg(feature = "std")]
     Io(io::Error),
     Decode(base64::DecodeError),
     Utf8Error(string::FromUtf8Error),
     InvalidCertType(u32),
     InvalidFormat,
-    UnexpectedEof,
+    EmptyInput,
+    UnexpectedEof {
+        offset: usize,
+        needed: usize,
+    },
     NotCertificate,
+    #[cfg_attr(feature = "std", allow(dead_code))]
+    MissingNonce,
     KeyTypeMismatch,
     UnknownKeyType(String),
+    #[cfg_attr(not(feature = "spki"), allow(dead_code))]
+    UnsupportedKeyType(String),
     UnknownCurve(String),
+    UnknownSignatureType(String),
+    CurveMismatch,
+    InvalidPoint,
+    WeakKey(String),
+    KeyTooLarge {
+        bits: usize,
+        max: usize,
+    },
+    TrailingData,
 }
 
 /// A `Result` type alias where the `Err` variant is `Error`
 pub type Result<T> = result::Result<T, Error>;
 
+#[cfg(feature = "std")]
 impl From<io::Error> for Error {
     fn from(error: io::Error) -> Error {
         Error {
@@ -58,6 +124,7 @@ impl From<string::FromUtf8Error> for Error {
     }
 }
 
+#[cfg(feature = "std")]
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self.kind {
@@ -66,11 +133,20 @@ impl StdError for Error {
             ErrorKind::Utf8Error(ref e) => e.source(),
             ErrorKind::InvalidCertType(_)
             | ErrorKind::InvalidFormat
-            | ErrorKind::UnexpectedEof
+            | ErrorKind::EmptyInput
+            | ErrorKind::UnexpectedEof { .. }
             | ErrorKind::NotCertificate
+            | ErrorKind::MissingNonce
             | ErrorKind::KeyTypeMismatch
             | ErrorKind::UnknownCurve(_)
-            | ErrorKind::UnknownKeyType(_) => None,
+            | ErrorKind::CurveMismatch
+            | ErrorKind::InvalidPoint
+            | ErrorKind::UnknownKeyType(_)
+            | ErrorKind::UnsupportedKeyType(_)
+            | ErrorKind::UnknownSignatureType(_)
+            | ErrorKind::WeakKey(_)
+            | ErrorKind::KeyTooLarge { .. }
+            | ErrorKind::TrailingData => None,
         }
     }
 }
@@ -78,16 +154,34 @@ impl StdError for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.kind {
+            #[cfg(feature = "std")]
             ErrorKind::Io(ref err) => err.fmt(f),
             ErrorKind::Decode(ref err) => err.fmt(f),
             ErrorKind::Utf8Error(ref err) => err.fmt(f),
             ErrorKind::InvalidFormat => write!(f, "Invalid format"),
+            ErrorKind::EmptyInput => write!(f, "Input is empty or contains only a comment"),
             ErrorKind::InvalidCertType(v) => write!(f, "Invalid certificate type with value {}", v),
-            ErrorKind::UnexpectedEof => write!(f, "Unexpected EOF reached while reading data"),
+            ErrorKind::UnexpectedEof { offset, needed } => write!(
+                f,
+                "Unexpected EOF reached while reading {} bytes at offset {}",
+                needed, offset
+            ),
             ErrorKind::UnknownKeyType(ref v) => write!(f, "Unknown key type {}", v),
+            ErrorKind::UnsupportedKeyType(ref v) => write!(f, "Unsupported key type {}", v),
             ErrorKind::NotCertificate => write!(f, "Not a certificate"),
+            ErrorKind::MissingNonce => write!(f, "Certificate nonce is missing"),
             ErrorKind::KeyTypeMismatch => write!(f, "Key type mismatch"),
             ErrorKind::UnknownCurve(ref v) => write!(f, "Unknown curve {}", v),
+            ErrorKind::UnknownSignatureType(ref v) => write!(f, "Unknown signature type {}", v),
+            ErrorKind::CurveMismatch => write!(f, "Curve does not match key type"),
+            ErrorKind::InvalidPoint => write!(f, "Invalid elliptic curve point"),
+            ErrorKind::WeakKey(ref v) => write!(f, "Weak key: {}", v),
+            ErrorKind::KeyTooLarge { bits, max } => write!(
+                f,
+                "Key of {} bits exceeds the maximum supported size of {} bits",
+                bits, max
+            ),
+            ErrorKind::TrailingData => write!(f, "Trailing data after key"),
         }
     }
 }
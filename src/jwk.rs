@@ -0,0 +1,105 @@
+extern crate serde_json;
+
+use self::serde_json::{Map, Value};
+use super::error::{Error, ErrorKind, Result};
+use super::pubkey::{coordinate_len, Curve, CurveKind, PublicKey};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use base64;
+
+impl PublicKey {
+    /// Reads a public key from a JSON Web Key (JWK) as described in RFC 7517.
+    ///
+    /// `RSA` keys are read from the `n` and `e` members, `EC` keys on the `P-256`, `P-384`,
+    /// `P-521` and `secp256k1` curves from the `x` and `y` coordinates, and `OKP` keys on
+    /// the `Ed25519` and `Ed448` curves from the `x` member, see RFC 7518 and RFC 8037.
+    /// Coordinates shorter than the size of the curve are padded with leading zeros.
+    /// All other members of the JWK, e.g. `kid` or `use`, are ignored.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let jwk = r#"{"kty": "OKP", "crv": "Ed25519", "x": "eRt7uDDHuzR2UCkQTOlTI4A-vM8vLhzMRnD-yHybyt0"}"#;
+    /// let key = sshkeys::PublicKey::from_jwk(jwk).unwrap();
+    /// assert_eq!(key.key_type.name, "ssh-ed25519");
+    /// assert_eq!(key.fingerprint().hash(), "ciQkdxjFUhk2E2vRkWJD9kB8pi+EneOkaCJJHNWzPC4");
+    /// ```
+    pub fn from_jwk(json: &str) -> Result<PublicKey> {
+        let jwk = match serde_json::from_str::<Value>(json) {
+            Ok(Value::Object(v)) => v,
+            _ => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
+
+        let kty = member(&jwk, "kty")?;
+        let key = match kty {
+            "RSA" => {
+                let n = decode_member(&jwk, "n")?;
+                let e = decode_member(&jwk, "e")?;
+
+                PublicKey::from_rsa(e, n)
+            }
+            "EC" => {
+                let curve = match member(&jwk, "crv")? {
+                    "P-256" => Curve::new(CurveKind::Nistp256),
+                    "P-384" => Curve::new(CurveKind::Nistp384),
+                    "P-521" => Curve::new(CurveKind::Nistp521),
+                    "secp256k1" => Curve::new(CurveKind::Secp256k1),
+                    crv => return Err(Error::with_kind(ErrorKind::UnknownCurve(crv.to_string()))),
+                };
+
+                // The point is encoded in uncompressed form, SEC 1, section 2.3.3
+                let len = coordinate_len(&curve);
+                let mut point = vec![0x04];
+                point.extend(pad_coordinate(decode_member(&jwk, "x")?, len)?);
+                point.extend(pad_coordinate(decode_member(&jwk, "y")?, len)?);
+
+                PublicKey::from_ecdsa(curve, point)
+            }
+            "OKP" => {
+                let crv = member(&jwk, "crv")?;
+                let x = decode_member(&jwk, "x")?;
+                match (crv, x.len()) {
+                    ("Ed25519", 32) => PublicKey::from_ed25519(x),
+                    ("Ed448", 57) => PublicKey::from_ed448(x),
+                    ("Ed25519", _) | ("Ed448", _) => {
+                        return Err(Error::with_kind(ErrorKind::InvalidFormat))
+                    }
+                    _ => return Err(Error::with_kind(ErrorKind::UnknownCurve(crv.to_string()))),
+                }
+            }
+            _ => return Err(Error::with_kind(ErrorKind::UnknownKeyType(kty.to_string()))),
+        };
+
+        Ok(key)
+    }
+}
+
+// Returns the value of a string member of a JWK.
+fn member<'a>(jwk: &'a Map<String, Value>, name: &str) -> Result<&'a str> {
+    match jwk.get(name) {
+        Some(Value::String(v)) => Ok(v),
+        _ => Err(Error::with_kind(ErrorKind::InvalidFormat)),
+    }
+}
+
+// Returns the decoded value of a base64url encoded member of a JWK, RFC 7515, section 2.
+// Padding is not used by JWKs, but is tolerated.
+fn decode_member(jwk: &Map<String, Value>, name: &str) -> Result<Vec<u8>> {
+    let value = member(jwk, name)?.trim_end_matches('=');
+    let decoded = base64::decode_config(value, base64::URL_SAFE_NO_PAD)?;
+
+    Ok(decoded)
+}
+
+// Pads a coordinate of a curve point with leading zeros to the given size.
+fn pad_coordinate(coordinate: Vec<u8>, len: usize) -> Result<Vec<u8>> {
+    if coordinate.len() > len {
+        return Err(Error::with_kind(ErrorKind::InvalidFormat));
+    }
+
+    let mut padded = vec![0; len - coordinate.len()];
+    padded.extend(coordinate);
+
+    Ok(padded)
+}
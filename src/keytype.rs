@@ -1,7 +1,9 @@
+use alloc::string::ToString;
+use core::fmt;
+
 use super::error::{Error, ErrorKind, Result};
-use std::fmt;
 /// A type which represents the various kinds of keys.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum KeyTypeKind {
     /// Represents an RSA key type.
     Rsa,
@@ -15,6 +17,18 @@ pub enum KeyTypeKind {
     /// Represents an ECDSA key type.
     Ecdsa,
 
+    /// Represents an ED448 key type.
+    Ed448,
+
+    /// Represents an ED25519 security key type.
+    SkEd25519,
+
+    /// Represents an ECDSA security key type.
+    SkEcdsa,
+
+    /// Represents an XMSS key type.
+    Xmss,
+
     /// Represents an RSA certificate key type.
     RsaCert,
 
@@ -26,10 +40,16 @@ pub enum KeyTypeKind {
 
     /// Represents an ECDSA certificate key type.
     EcdsaCert,
+
+    /// Represents an ED25519 security key certificate key type.
+    SkEd25519Cert,
+
+    /// Represents an ECDSA security key certificate key type.
+    SkEcdsaCert,
 }
 
 /// `KeyType` represents the type of an OpenSSH key.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct KeyType {
     /// Name of the key type.
     pub name: &'static str,
@@ -50,13 +70,28 @@ pub struct KeyType {
 impl KeyType {
     /// Creates a new `KeyType` from a given name.
     ///
+    /// Surrounding whitespace, e.g. a carriage return left over from a Windows line
+    /// ending, is ignored. Otherwise the name must match one of the known names exactly,
+    /// including the case, the same way as OpenSSH compares key type names.
+    ///
     /// # Example
     /// ```rust
     /// # use sshkeys;
     /// let kt = sshkeys::KeyType::from_name("ssh-rsa").unwrap();
     /// assert_eq!(kt.kind, sshkeys::KeyTypeKind::Rsa);
+    ///
+    /// let kt = sshkeys::KeyType::from_name("ssh-rsa\r").unwrap();
+    /// assert_eq!(kt.name, "ssh-rsa");
+    ///
+    /// assert!(sshkeys::KeyType::from_name("SSH-RSA").is_err());
     /// ```
     pub fn from_name(name: &str) -> Result<KeyType> {
+        KeyType::from_exact_name(name.trim())
+    }
+
+    // Creates a new `KeyType` from a name which matches one of the known names exactly,
+    // which is used for the key type names in key blobs.
+    pub(crate) fn from_exact_name(name: &str) -> Result<KeyType> {
         let kt = match name {
             "ssh-rsa" => KeyType {
                 name: "ssh-rsa",
@@ -107,6 +142,13 @@ impl KeyType {
                 is_cert: false,
                 kind: KeyTypeKind::Ecdsa,
             },
+            "ecdsa-sha2-secp256k1" => KeyType {
+                name: "ecdsa-sha2-secp256k1",
+                plain: "ecdsa-sha2-secp256k1",
+                short_name: "ECDSA",
+                is_cert: false,
+                kind: KeyTypeKind::Ecdsa,
+            },
             "ecdsa-sha2-nistp256-cert-v01@openssh.com" => KeyType {
                 name: "ecdsa-sha2-nistp256-cert-v01@openssh.com",
                 plain: "ecdsa-sha2-nistp256",
@@ -142,6 +184,48 @@ impl KeyType {
                 is_cert: true,
                 kind: KeyTypeKind::Ed25519Cert,
             },
+            "ssh-ed448" => KeyType {
+                name: "ssh-ed448",
+                plain: "ssh-ed448",
+                short_name: "ED448",
+                is_cert: false,
+                kind: KeyTypeKind::Ed448,
+            },
+            "sk-ssh-ed25519@openssh.com" => KeyType {
+                name: "sk-ssh-ed25519@openssh.com",
+                plain: "sk-ssh-ed25519@openssh.com",
+                short_name: "ED25519-SK",
+                is_cert: false,
+                kind: KeyTypeKind::SkEd25519,
+            },
+            "sk-ssh-ed25519-cert-v01@openssh.com" => KeyType {
+                name: "sk-ssh-ed25519-cert-v01@openssh.com",
+                plain: "sk-ssh-ed25519@openssh.com",
+                short_name: "ED25519-SK-CERT",
+                is_cert: true,
+                kind: KeyTypeKind::SkEd25519Cert,
+            },
+            "sk-ecdsa-sha2-nistp256@openssh.com" => KeyType {
+                name: "sk-ecdsa-sha2-nistp256@openssh.com",
+                plain: "sk-ecdsa-sha2-nistp256@openssh.com",
+                short_name: "ECDSA-SK",
+                is_cert: false,
+                kind: KeyTypeKind::SkEcdsa,
+            },
+            "sk-ecdsa-sha2-nistp256-cert-v01@openssh.com" => KeyType {
+                name: "sk-ecdsa-sha2-nistp256-cert-v01@openssh.com",
+                plain: "sk-ecdsa-sha2-nistp256@openssh.com",
+                short_name: "ECDSA-SK-CERT",
+                is_cert: true,
+                kind: KeyTypeKind::SkEcdsaCert,
+            },
+            "ssh-xmss@openssh.com" => KeyType {
+                name: "ssh-xmss@openssh.com",
+                plain: "ssh-xmss@openssh.com",
+                short_name: "XMSS",
+                is_cert: false,
+                kind: KeyTypeKind::Xmss,
+            },
             _ => {
                 return Err(Error::with_kind(ErrorKind::UnknownKeyType(
                     name.to_string(),
@@ -151,6 +235,39 @@ impl KeyType {
 
         Ok(kt)
     }
+
+    // Creates a new `KeyType` from the key type token of a key line in text form.
+    // Surrounding whitespace, e.g. a carriage return left over from a Windows line ending,
+    // is ignored, and the token is matched case-insensitively against the known names.
+    pub(crate) fn from_token(token: &str) -> Result<KeyType> {
+        let token = token.trim();
+        if let Ok(kt) = KeyType::from_exact_name(token) {
+            return Ok(kt);
+        }
+
+        match KeyType::from_exact_name(&token.to_ascii_lowercase()) {
+            Ok(kt) => Ok(kt),
+            Err(_) => Err(Error::with_kind(ErrorKind::UnknownKeyType(
+                token.to_string(),
+            ))),
+        }
+    }
+
+    /// Returns `true` if the key type represents a certificate.
+    ///
+    /// This is the same as the `is_cert` field, and is provided for use in
+    /// method chains and closures.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let kt = sshkeys::KeyType::from_name("ssh-ed25519-cert-v01@openssh.com").unwrap();
+    /// assert!(kt.is_cert());
+    /// assert_eq!(kt.is_cert(), kt.is_cert);
+    /// ```
+    pub fn is_cert(&self) -> bool {
+        self.is_cert
+    }
 }
 
 impl fmt::Display for KeyType {
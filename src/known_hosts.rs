@@ -0,0 +1,232 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{BufRead, BufReader, Read};
+
+use super::error::{Error, ErrorKind, Result};
+use super::pubkey::PublicKey;
+
+use base64;
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// Represents the markers an entry in a `known_hosts` file can have.
+#[derive(Debug, PartialEq)]
+pub enum KnownHostMarker {
+    /// The key is a certification authority key, which is trusted
+    /// to sign host certificates for the matching hosts.
+    CertAuthority,
+
+    /// The key has been revoked and must never be accepted.
+    Revoked,
+}
+
+/// Represents the hosts part of an entry in a `known_hosts` file.
+#[derive(Debug, PartialEq)]
+pub enum HostPatterns {
+    /// A list of host patterns, which were separated by commas.
+    Patterns(Vec<String>),
+
+    /// A hashed hostname in the `|1|salt|hash` format.
+    Hashed(String),
+}
+
+/// A type which represents an entry in an OpenSSH `known_hosts` file.
+/// Please refer to the `SSH_KNOWN_HOSTS FILE FORMAT` section of sshd(8) for more details.
+#[derive(Debug)]
+pub struct KnownHost {
+    /// The marker of the entry, if any.
+    pub marker: Option<KnownHostMarker>,
+
+    /// The hosts to which the entry applies.
+    pub hosts: HostPatterns,
+
+    /// The public key of the hosts.
+    pub key: PublicKey,
+}
+
+impl KnownHost {
+    /// Reads a `known_hosts` entry from a given line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let entry = sshkeys::KnownHost::from_string("example.com,10.0.0.1 ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd").unwrap();
+    /// assert_eq!(entry.marker, None);
+    /// assert_eq!(entry.hosts, sshkeys::HostPatterns::Patterns(vec!["example.com".to_string(), "10.0.0.1".to_string()]));
+    /// ```
+    pub fn from_string(line: &str) -> Result<KnownHost> {
+        let (mut token, mut rest) = split_token(line.trim());
+
+        let marker = if token.starts_with('@') {
+            let marker = match token {
+                "@cert-authority" => KnownHostMarker::CertAuthority,
+                "@revoked" => KnownHostMarker::Revoked,
+                _ => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+            };
+
+            let next = split_token(rest);
+            token = next.0;
+            rest = next.1;
+
+            Some(marker)
+        } else {
+            None
+        };
+
+        if token.is_empty() {
+            return Err(Error::with_kind(ErrorKind::InvalidFormat));
+        }
+
+        let hosts = if token.starts_with("|1|") {
+            HostPatterns::Hashed(token.to_string())
+        } else {
+            HostPatterns::Patterns(token.split(',').map(String::from).collect())
+        };
+
+        let entry = KnownHost {
+            marker,
+            hosts,
+            key: PublicKey::from_string(rest)?,
+        };
+
+        Ok(entry)
+    }
+
+    /// Returns `true` if the given hostname matches the hosts of the entry.
+    ///
+    /// Hashed hostnames are matched by computing the HMAC-SHA1 of the hostname
+    /// using the salt of the entry. Host patterns may contain the `*` and `?`
+    /// wildcards, and patterns prefixed with `!` negate the match, in which
+    /// case the hostname is not matched even if another pattern matches it.
+    ///
+    /// For hosts on a non-standard port the hostname is expected in
+    /// the `[host]:port` format, the same way it appears in the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let entry = sshkeys::KnownHost::from_string("*.example.com,!bad.example.com ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd").unwrap();
+    /// assert!(entry.matches_host("www.example.com"));
+    /// assert!(!entry.matches_host("bad.example.com"));
+    /// assert!(!entry.matches_host("example.org"));
+    /// ```
+    pub fn matches_host(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+
+        match self.hosts {
+            HostPatterns::Hashed(ref hashed) => matches_hashed(hashed, &host),
+            HostPatterns::Patterns(ref patterns) => {
+                let mut matched = false;
+                for pattern in patterns {
+                    let pattern = pattern.to_lowercase();
+                    match pattern.strip_prefix('!') {
+                        Some(negated) if matches_wildcard(negated, &host) => return false,
+                        Some(_) => {}
+                        None if matches_wildcard(&pattern, &host) => matched = true,
+                        None => {}
+                    }
+                }
+
+                matched
+            }
+        }
+    }
+}
+
+// Matches a hostname against a hashed hostname in the `|1|salt|hash` format.
+fn matches_hashed(hashed: &str, host: &str) -> bool {
+    let mut parts = hashed.trim_start_matches("|1|").splitn(2, '|');
+    let (salt, hash) = match (parts.next(), parts.next()) {
+        (Some(s), Some(h)) => (s, h),
+        _ => return false,
+    };
+
+    let (salt, hash) = match (base64::decode(salt), base64::decode(hash)) {
+        (Ok(s), Ok(h)) => (s, h),
+        _ => return false,
+    };
+
+    let mut mac = match Hmac::<Sha1>::new_varkey(&salt) {
+        Ok(m) => m,
+        Err(_) => return false,
+    };
+    mac.input(host.as_bytes());
+
+    // Verification of the code is done in constant time
+    mac.verify(&hash).is_ok()
+}
+
+// Matches a string against a pattern, which may contain the `*` wildcard
+// matching any sequence of characters, and the `?` wildcard matching
+// exactly one character.
+fn matches_wildcard(pattern: &str, s: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+
+    let (mut p, mut i) = (0, 0);
+    // Position of the last `*` in the pattern and the position
+    // in the string it was matched against, used for backtracking.
+    let mut star: Option<(usize, usize)> = None;
+
+    while i < s.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == s[i]) {
+            p += 1;
+            i += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, i));
+            p += 1;
+        } else if let Some((sp, si)) = star {
+            p = sp + 1;
+            i = si + 1;
+            star = Some((sp, si + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Reads all entries from an OpenSSH `known_hosts` file.
+///
+/// Blank lines and lines starting with `#` are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// # use sshkeys;
+/// # fn example() -> sshkeys::Result<()> {
+/// let file = std::fs::File::open("/path/to/known_hosts")?;
+/// let entries = sshkeys::parse_known_hosts(file)?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_known_hosts<R: Read>(r: R) -> Result<Vec<KnownHost>> {
+    let mut entries = Vec::new();
+
+    for line in BufReader::new(r).lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        entries.push(KnownHost::from_string(trimmed)?);
+    }
+
+    Ok(entries)
+}
+
+// Splits a string at the first whitespace character and
+// strips the leading whitespace from the remainder.
+fn split_token(s: &str) -> (&str, &str) {
+    match s.find(char::is_whitespace) {
+        Some(offset) => (&s[..offset], s[offset..].trim_start()),
+        None => (s, ""),
+    }
+}
@@ -1,3 +1,4 @@
+#![no_std]
 #![deny(warnings)]
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
@@ -9,8 +10,12 @@
 //!
 //! - RSA
 //! - DSA
-//! - ECDSA
+//! - ECDSA (NIST P-256, P-384, P-521 and secp256k1 curves)
 //! - ED25519
+//! - ED448
+//! - ED25519 security keys (sk-ssh-ed25519@openssh.com)
+//! - ECDSA security keys (sk-ecdsa-sha2-nistp256@openssh.com)
+//! - XMSS (ssh-xmss@openssh.com), parsing and encoding only
 //!
 //! The following OpenSSH certificate types are supported as well.
 //!
@@ -20,33 +25,114 @@
 //! - ecdsa-sha2-nistp384-cert-v01@openssh.com
 //! - ecdsa-sha2-nistp512-cert-v01@openssh.com
 //! - ssh-ed25519-cert-v01@openssh.com
+//! - sk-ssh-ed25519-cert-v01@openssh.com
+//! - sk-ecdsa-sha2-nistp256-cert-v01@openssh.com
+//!
+//! # Features
+//!
+//! The `std` feature is enabled by default. When it is disabled the crate
+//! only depends on `core` and `alloc`, and the functionality which requires
+//! the filesystem or `std::io` is not available.
+//!
+//! The optional `verify` feature adds support for verifying ED25519, RSA, ECDSA and DSA
+//! signatures, including the CA signatures of certificates.
+//!
+//! The optional `jwk` feature adds `PublicKey::from_jwk` for reading RSA, ECDSA, ED25519
+//! and ED448 public keys from JSON Web Keys (RFC 7517).
+//!
+//! The optional `spki` feature adds `PublicKey::from_spki_der` and `PublicKey::to_spki_der`
+//! for converting RSA, DSA, ECDSA, ED25519 and ED448 public keys from and to DER encoded
+//! SubjectPublicKeyInfo structures.
+//!
+//! The optional `bigint` feature adds `Reader::read_biguint` and accessors such as
+//! `RsaPublicKey::modulus_biguint` for reading `mpint` values as `num_bigint::BigUint`.
+//!
+//! The optional `zeroize` feature implements `Zeroize` for public keys, signatures and
+//! certificates, so that their byte sequences can be cleared with `zeroize()`. They
+//! are not cleared when they are dropped, so that their fields can still be moved out,
+//! wrap them in `zeroize::Zeroizing`, e.g. `Zeroizing<PublicKey>`, in order to clear
+//! them on drop.
 //!
 //! # Examples
 //!
 //! In order to view examples of this crate in use, please refer to the
 //! `examples` directory.
 
+#[cfg(feature = "std")]
+extern crate std;
+
+#[macro_use]
+extern crate alloc;
+
 extern crate base64;
 extern crate byteorder;
+#[cfg(feature = "verify")]
+extern crate dsa;
+#[cfg(feature = "verify")]
+extern crate ed25519_dalek;
+#[cfg(feature = "std")]
+extern crate getrandom;
+extern crate hmac;
+extern crate md5;
+#[cfg(feature = "verify")]
+extern crate p256;
+#[cfg(feature = "verify")]
+extern crate p384;
+#[cfg(feature = "verify")]
+extern crate p521;
+#[cfg(feature = "verify")]
+extern crate rsa;
+extern crate sha1;
 extern crate sha2;
 
+mod agent;
+mod authorized_keys;
 mod cert;
 mod error;
 mod keytype;
+mod known_hosts;
 mod pubkey;
 mod reader;
+mod signature;
 mod writer;
 
 // Serialization and deserialization support for sshkeys
 #[cfg(feature = "serde")]
 mod serde;
 
-pub use self::cert::{CertType, Certificate};
+// Conversion of mpint values to unsigned big integers
+#[cfg(feature = "bigint")]
+mod bigint;
+
+// Reading of public keys from JSON Web Keys
+#[cfg(feature = "jwk")]
+mod jwk;
+
+// Conversion of public keys from and to SubjectPublicKeyInfo structures
+#[cfg(feature = "spki")]
+mod spki;
+
+// Zeroing of the byte sequences of keys and certificates
+#[cfg(feature = "zeroize")]
+mod zeroize;
+
+pub use self::agent::parse_agent_identities;
+pub use self::authorized_keys::AuthorizedKey;
+#[cfg(feature = "std")]
+pub use self::authorized_keys::{parse_authorized_keys, parse_authorized_keys_lenient};
+pub use self::cert::{CertType, Certificate, CertificateBuilder};
 pub use self::error::{Error, Result};
 pub use self::keytype::{KeyType, KeyTypeKind};
+#[cfg(feature = "std")]
+pub use self::known_hosts::parse_known_hosts;
+pub use self::known_hosts::{HostPatterns, KnownHost, KnownHostMarker};
+#[cfg(feature = "verify")]
+pub use self::pubkey::RsaHash;
 pub use self::pubkey::{
-    Curve, CurveKind, DsaPublicKey, EcdsaPublicKey, Ed25519PublicKey, Fingerprint, FingerprintKind,
-    PublicKey, PublicKeyKind, RsaPublicKey,
+    Curve, CurveKind, DsaPublicKey, EcdsaPublicKey, EcdsaSkPublicKey, Ed25519PublicKey,
+    Ed25519SkPublicKey, Ed448PublicKey, Fingerprint, FingerprintKind, PublicKey, PublicKeyKind,
+    RsaPublicKey, XmssPublicKey, RSA_MAX_BITS,
 };
 pub use self::reader::Reader;
+pub use self::signature::{EcdsaSignature, Signature, SignatureKind};
 pub use self::writer::Writer;
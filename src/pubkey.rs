@@ -1,6 +1,15 @@
-use std::fmt;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
+use core::iter;
+use core::str::FromStr;
+#[cfg(feature = "std")]
 use std::fs::File;
-use std::io::{self, Read};
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, BufReader, Read};
+#[cfg(feature = "std")]
 use std::path::Path;
 
 use super::error::{Error, ErrorKind, Result};
@@ -10,10 +19,22 @@ use super::writer::Writer;
 
 use base64;
 
+use core::convert::TryFrom;
+#[cfg(feature = "verify")]
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+#[cfg(feature = "verify")]
+use p256::ecdsa::signature::hazmat::PrehashVerifier;
+#[cfg(feature = "verify")]
+use rsa::traits::PublicKeyParts;
+#[cfg(feature = "verify")]
+use rsa::{BigUint, Pkcs1v15Sign};
+use sha1::Sha1;
+
+use md5::Md5;
 use sha2::{Digest, Sha256, Sha384, Sha512};
 
 /// A type which represents the different kinds a public key can be.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PublicKeyKind {
     /// Represents an RSA public key.
     Rsa(RsaPublicKey),
@@ -26,11 +47,23 @@ pub enum PublicKeyKind {
 
     /// Represents an ED25519 public key.
     Ed25519(Ed25519PublicKey),
+
+    /// Represents an ED448 public key.
+    Ed448(Ed448PublicKey),
+
+    /// Represents an ED25519 security key (FIDO/U2F) public key.
+    SkEd25519(Ed25519SkPublicKey),
+
+    /// Represents an ECDSA security key (FIDO/U2F) public key.
+    SkEcdsa(EcdsaSkPublicKey),
+
+    /// Represents an XMSS public key.
+    Xmss(XmssPublicKey),
 }
 
 /// RSA public key.
 /// The format of RSA public keys is described in RFC 4253, section 6.6
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RsaPublicKey {
     /// Exponent of key.
     pub e: Vec<u8>,
@@ -39,9 +72,331 @@ pub struct RsaPublicKey {
     pub n: Vec<u8>,
 }
 
+// The minimum RSA modulus size accepted by `RsaPublicKey::validate`.
+// This is the same minimum as enforced by OpenSSH.
+const RSA_MIN_BITS: usize = 1024;
+
+/// The maximum size in bits of the modulus of RSA public keys, which are read by this crate.
+///
+/// Keys with a larger modulus are rejected when they are parsed, which protects against
+/// resource exhaustion by hostile keys. This is the same maximum as enforced by OpenSSH.
+pub const RSA_MAX_BITS: usize = 16384;
+
+// RSA keys with a modulus smaller than this are reported by `PublicKey::is_deprecated`.
+const RSA_RECOMMENDED_BITS: usize = 2048;
+
+// The security strength in bits of RSA and DSA keys with a modulus of at least the given
+// size, as estimated by NIST SP 800-57 Part 1 Rev. 5, section 5.6.1.1, table 2.
+const FFC_IFC_SECURITY_BITS: &[(usize, u32)] = &[
+    (15360, 256),
+    (7680, 192),
+    (3072, 128),
+    (2048, 112),
+    (1024, 80),
+];
+
+// Returns the security strength in bits of an RSA or DSA key with a modulus of the given
+// size. Keys smaller than the smallest size of the table provide no meaningful security.
+fn modulus_security_bits(bits: usize) -> u32 {
+    FFC_IFC_SECURITY_BITS
+        .iter()
+        .find(|&&(size, _)| bits >= size)
+        .map(|&(_, security)| security)
+        .unwrap_or(0)
+}
+
+impl RsaPublicKey {
+    /// Validates the RSA public key by checking that the modulus is at least
+    /// 1024 bits long and that the exponent is odd and greater than one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let key = sshkeys::PublicKey::from_path("/path/to/id_rsa.pub")?;
+    /// if let sshkeys::PublicKeyKind::Rsa(ref k) = key.kind {
+    ///     k.validate()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        self.validate_with_min_bits(RSA_MIN_BITS)
+    }
+
+    /// Validates the RSA public key the same way `validate` does, but
+    /// using the given minimum modulus size in bits.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::RsaPublicKey {
+    ///     e: vec![1, 0, 1],
+    ///     n: vec![0xc5; 128],
+    /// };
+    /// assert!(key.validate_with_min_bits(1024).is_ok());
+    /// assert!(key.validate_with_min_bits(2048).is_err());
+    /// ```
+    pub fn validate_with_min_bits(&self, min_bits: usize) -> Result<()> {
+        let bits = mpint_bits(&self.n);
+        if bits < min_bits {
+            return Err(Error::with_kind(ErrorKind::WeakKey(format!(
+                "RSA modulus of {} bits is smaller than {} bits",
+                bits, min_bits
+            ))));
+        }
+
+        let e = strip_leading_zeros(&self.e);
+        if e.last().is_none_or(|b| b & 1 == 0) {
+            return Err(Error::with_kind(ErrorKind::WeakKey(
+                "RSA exponent is even".to_string(),
+            )));
+        }
+
+        if e == [1] {
+            return Err(Error::with_kind(ErrorKind::WeakKey(
+                "RSA exponent is one".to_string(),
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// The hash algorithms used with RSA signatures.
+#[cfg(feature = "verify")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RsaHash {
+    /// SHA-1, as used by `ssh-rsa` signatures.
+    Sha1,
+
+    /// SHA-256, as used by `rsa-sha2-256` signatures.
+    Sha256,
+
+    /// SHA-512, as used by `rsa-sha2-512` signatures.
+    Sha512,
+}
+
+#[cfg(feature = "verify")]
+impl RsaHash {
+    // Returns the digest of the message and the DER encoded `DigestInfo` prefix,
+    // which precedes the digest in PKCS #1 v1.5 signatures, RFC 8017, section 9.2.
+    fn digest(self, message: &[u8]) -> (Vec<u8>, &'static [u8]) {
+        match self {
+            RsaHash::Sha1 => (
+                Sha1::digest(message).to_vec(),
+                &[
+                    0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00,
+                    0x04, 0x14,
+                ],
+            ),
+            RsaHash::Sha256 => (
+                Sha256::digest(message).to_vec(),
+                &[
+                    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04,
+                    0x02, 0x01, 0x05, 0x00, 0x04, 0x20,
+                ],
+            ),
+            RsaHash::Sha512 => (
+                Sha512::digest(message).to_vec(),
+                &[
+                    0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04,
+                    0x02, 0x03, 0x05, 0x00, 0x04, 0x40,
+                ],
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "verify")]
+impl RsaPublicKey {
+    /// Verifies a PKCS #1 v1.5 RSA signature of the given message using the given hash algorithm.
+    ///
+    /// The signature is the raw RSA signature, and not the signature blob in the SSH wire format.
+    /// Returns `Ok(false)` if the signature does not match, and an error if the key is malformed.
+    ///
+    /// This method is only available with the `verify` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example(key: &sshkeys::RsaPublicKey, message: &[u8], signature: &[u8]) -> sshkeys::Result<()> {
+    /// if key.verify(message, signature, sshkeys::RsaHash::Sha512)? {
+    ///     println!("Valid signature");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify(&self, message: &[u8], signature: &[u8], hash: RsaHash) -> Result<bool> {
+        let key = match rsa::RsaPublicKey::new(
+            BigUint::from_bytes_be(&self.n),
+            BigUint::from_bytes_be(&self.e),
+        ) {
+            Ok(k) => k,
+            Err(_) => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
+
+        let (digest, prefix) = hash.digest(message);
+        let scheme = Pkcs1v15Sign {
+            hash_len: Some(digest.len()),
+            prefix: prefix.into(),
+        };
+
+        // Signatures shorter than the modulus are accepted by OpenSSH, once padded with zeros
+        let mut padded = vec![0; key.size().saturating_sub(signature.len())];
+        padded.extend_from_slice(signature);
+
+        Ok(key.verify(scheme, &digest, &padded).is_ok())
+    }
+}
+
+// Reads the curve identifier of an ECDSA public key and verifies
+// that it is the same curve as the one specified by the key type name.
+fn read_curve(kt: &KeyType, reader: &mut Reader) -> Result<Curve> {
+    let identifier = reader.read_string()?;
+    let curve = Curve::from_identifier(&identifier)?;
+
+    let expected = match kt.kind {
+        // Security keys support only the NIST P-256 curve
+        KeyTypeKind::SkEcdsa | KeyTypeKind::SkEcdsaCert => "nistp256",
+        _ => kt.plain.trim_start_matches("ecdsa-sha2-"),
+    };
+
+    if curve.identifier != expected {
+        return Err(Error::with_kind(ErrorKind::CurveMismatch));
+    }
+
+    Ok(curve)
+}
+
+// Reads the public key point of an ECDSA public key and verifies that it is an
+// uncompressed point of the expected size for the curve, i.e. the `0x04` prefix
+// followed by both coordinates, as described in SEC 1, section 2.3.3.
+fn read_point(curve: &Curve, reader: &mut Reader) -> Result<Vec<u8>> {
+    let point = reader.read_bytes()?;
+
+    if point.len() != 1 + 2 * coordinate_len(curve) || point[0] != 0x04 {
+        return Err(Error::with_kind(ErrorKind::InvalidPoint));
+    }
+
+    Ok(point)
+}
+
+// Validates an ECDSA public key point by checking that it is
+// an uncompressed point with coordinates which are not all zeros.
+fn validate_point(point: &[u8]) -> Result<()> {
+    match point.split_first() {
+        Some((&0x04, coords)) if coords.iter().any(|b| *b != 0) => Ok(()),
+        Some((&0x04, _)) => Err(Error::with_kind(ErrorKind::WeakKey(
+            "ECDSA point is zero".to_string(),
+        ))),
+        _ => Err(Error::with_kind(ErrorKind::InvalidPoint)),
+    }
+}
+
+// The encodings of the ED25519 points of small order, which must never be used as public keys.
+const ED25519_LOW_ORDER_POINTS: [[u8; 32]; 8] = [
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ],
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x80,
+    ],
+    [
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ],
+    [
+        0xec, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x7f,
+    ],
+    [
+        0x26, 0xe8, 0x95, 0x8f, 0xc2, 0xb2, 0x27, 0xb0, 0x45, 0xc3, 0xf4, 0x89, 0xf2, 0xef, 0x98,
+        0xf0, 0xd5, 0xdf, 0xac, 0x05, 0xd3, 0xc6, 0x33, 0x39, 0xb1, 0x38, 0x02, 0x88, 0x6d, 0x53,
+        0xfc, 0x05,
+    ],
+    [
+        0x26, 0xe8, 0x95, 0x8f, 0xc2, 0xb2, 0x27, 0xb0, 0x45, 0xc3, 0xf4, 0x89, 0xf2, 0xef, 0x98,
+        0xf0, 0xd5, 0xdf, 0xac, 0x05, 0xd3, 0xc6, 0x33, 0x39, 0xb1, 0x38, 0x02, 0x88, 0x6d, 0x53,
+        0xfc, 0x85,
+    ],
+    [
+        0xc7, 0x17, 0x6a, 0x70, 0x3d, 0x4d, 0xd8, 0x4f, 0xba, 0x3c, 0x0b, 0x76, 0x0d, 0x10, 0x67,
+        0x0f, 0x2a, 0x20, 0x53, 0xfa, 0x2c, 0x39, 0xcc, 0xc6, 0x4e, 0xc7, 0xfd, 0x77, 0x92, 0xac,
+        0x03, 0x7a,
+    ],
+    [
+        0xc7, 0x17, 0x6a, 0x70, 0x3d, 0x4d, 0xd8, 0x4f, 0xba, 0x3c, 0x0b, 0x76, 0x0d, 0x10, 0x67,
+        0x0f, 0x2a, 0x20, 0x53, 0xfa, 0x2c, 0x39, 0xcc, 0xc6, 0x4e, 0xc7, 0xfd, 0x77, 0x92, 0xac,
+        0x03, 0xfa,
+    ],
+];
+
+// Validates an ED25519 public key by checking its size and that it is not a point of small order.
+fn validate_ed25519(key: &[u8]) -> Result<()> {
+    if key.len() != 32 {
+        return Err(Error::with_kind(ErrorKind::InvalidFormat));
+    }
+
+    if ED25519_LOW_ORDER_POINTS.iter().any(|p| p[..] == *key) {
+        return Err(Error::with_kind(ErrorKind::WeakKey(
+            "ED25519 point has small order".to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
+// Returns the size of a single coordinate of a point on the given curve.
+pub(crate) fn coordinate_len(curve: &Curve) -> usize {
+    match curve.kind {
+        CurveKind::Nistp256 | CurveKind::Secp256k1 => 32,
+        CurveKind::Nistp384 => 48,
+        CurveKind::Nistp521 => 66,
+    }
+}
+
+// Returns the byte sequence of a big-endian number without its leading zero bytes.
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let offset = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+
+    &bytes[offset..]
+}
+
+// Returns the encoded size of a `string` value, i.e. the length prefix followed by the value.
+// This must be kept in sync with `Writer::write_bytes`.
+fn bytes_len<T: ?Sized + AsRef<[u8]>>(val: &T) -> usize {
+    4 + val.as_ref().len()
+}
+
+// Returns the encoded size of an `mpint` value.
+// This must be kept in sync with `Writer::write_mpint`.
+fn mpint_len(val: &[u8]) -> usize {
+    let val = strip_leading_zeros(val);
+    match val.first() {
+        Some(b) if b & 0x80 != 0 => bytes_len(val) + 1,
+        _ => bytes_len(val),
+    }
+}
+
+// Returns the number of significant bits of a big-endian number.
+pub(crate) fn mpint_bits(bytes: &[u8]) -> usize {
+    let bytes = strip_leading_zeros(bytes);
+    match bytes.first() {
+        Some(b) => bytes.len() * 8 - b.leading_zeros() as usize,
+        None => 0,
+    }
+}
+
 /// DSA public key.
 /// The format of DSA public keys is described in RFC 4253, section 6.6
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DsaPublicKey {
     /// Parameter `p`.
     pub p: Vec<u8>,
@@ -56,8 +411,108 @@ pub struct DsaPublicKey {
     pub y: Vec<u8>,
 }
 
+// The minimum sizes of the DSA `p` and `q` parameters accepted by `DsaPublicKey::validate`.
+const DSA_MIN_P_BITS: usize = 1024;
+const DSA_MIN_Q_BITS: usize = 160;
+
+impl DsaPublicKey {
+    /// Validates the DSA public key by checking that the primes `p` and `q` are
+    /// at least 1024 and 160 bits long, and that `g` and `y` are in the range `2..p`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::DsaPublicKey {
+    ///     p: vec![0],
+    ///     q: vec![0],
+    ///     g: vec![0],
+    ///     y: vec![0],
+    /// };
+    /// assert!(key.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        if mpint_bits(&self.p) < DSA_MIN_P_BITS || mpint_bits(&self.q) < DSA_MIN_Q_BITS {
+            return Err(Error::with_kind(ErrorKind::WeakKey(
+                "DSA primes are too small".to_string(),
+            )));
+        }
+
+        let p = strip_leading_zeros(&self.p);
+        for v in &[&self.g, &self.y] {
+            let v = strip_leading_zeros(v);
+            if mpint_bits(v) < 2 || (v.len(), v) >= (p.len(), p) {
+                return Err(Error::with_kind(ErrorKind::WeakKey(
+                    "DSA parameters are out of range".to_string(),
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Size of the `r` and `s` values of `ssh-dss` signatures, RFC 4253, section 6.6
+#[cfg(feature = "verify")]
+const DSA_SIGNATURE_VALUE_LEN: usize = 20;
+
+#[cfg(feature = "verify")]
+impl DsaPublicKey {
+    /// Verifies a DSA signature of the given message.
+    ///
+    /// The signature is the raw 40 bytes `ssh-dss` signature, i.e. the `r` and `s` values
+    /// as 20 bytes unsigned integers, as described in RFC 4253, section 6.6, and not the
+    /// signature blob in the SSH wire format. The message is hashed using SHA-1.
+    /// Returns `Ok(false)` if the signature does not match, and an error if the key or
+    /// the signature is malformed.
+    ///
+    /// DSA keys are deprecated and disabled by default since OpenSSH 7.0, so this should
+    /// only be used for validating existing legacy signatures.
+    ///
+    /// This method is only available with the `verify` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example(key: &sshkeys::DsaPublicKey, message: &[u8], signature: &[u8]) -> sshkeys::Result<()> {
+    /// if key.verify(message, signature)? {
+    ///     println!("Valid signature");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        let key = match dsa::Components::from_components(
+            BigUint::from_bytes_be(&self.p),
+            BigUint::from_bytes_be(&self.q),
+            BigUint::from_bytes_be(&self.g),
+        )
+        .and_then(|c| dsa::VerifyingKey::from_components(c, BigUint::from_bytes_be(&self.y)))
+        {
+            Ok(k) => k,
+            Err(_) => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
+
+        if signature.len() != 2 * DSA_SIGNATURE_VALUE_LEN {
+            return Err(Error::with_kind(ErrorKind::InvalidFormat));
+        }
+
+        let (r, s) = signature.split_at(DSA_SIGNATURE_VALUE_LEN);
+        let signature = match dsa::Signature::from_components(
+            BigUint::from_bytes_be(r),
+            BigUint::from_bytes_be(s),
+        ) {
+            Ok(s) => s,
+            Err(_) => return Ok(false),
+        };
+
+        Ok(key
+            .verify_prehash(&Sha1::digest(message), &signature)
+            .is_ok())
+    }
+}
+
 /// Represents the different kinds of supported curves.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CurveKind {
     /// Represents a NIST P-256 curve.
     Nistp256,
@@ -67,10 +522,13 @@ pub enum CurveKind {
 
     /// Represents a NIST P-521 curve.
     Nistp521,
+
+    /// Represents a SECG secp256k1 curve.
+    Secp256k1,
 }
 
 /// A type which represents a cryptographic curve.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Curve {
     /// The curve kind.
     pub kind: CurveKind,
@@ -80,6 +538,26 @@ pub struct Curve {
 }
 
 impl Curve {
+    /// Creates a new `Curve` of the given kind with the canonical identifier of the curve.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let curve = sshkeys::Curve::new(sshkeys::CurveKind::Nistp384);
+    /// assert_eq!(curve.identifier, "nistp384");
+    /// assert_eq!(curve, sshkeys::Curve::from_identifier("nistp384").unwrap());
+    /// ```
+    pub fn new(kind: CurveKind) -> Curve {
+        let identifier = match kind {
+            CurveKind::Nistp256 => "nistp256",
+            CurveKind::Nistp384 => "nistp384",
+            CurveKind::Nistp521 => "nistp521",
+            CurveKind::Secp256k1 => "secp256k1",
+        };
+
+        Curve { kind, identifier }
+    }
+
     /// Creates a new `Curve` from the given identifier.
     ///
     /// # Example
@@ -89,29 +567,21 @@ impl Curve {
     /// assert_eq!(curve.kind, sshkeys::CurveKind::Nistp256);
     /// ```
     pub fn from_identifier(id: &str) -> Result<Curve> {
-        let curve = match id {
-            "nistp256" => Curve {
-                kind: CurveKind::Nistp256,
-                identifier: "nistp256",
-            },
-            "nistp384" => Curve {
-                kind: CurveKind::Nistp384,
-                identifier: "nistp384",
-            },
-            "nistp521" => Curve {
-                kind: CurveKind::Nistp521,
-                identifier: "nistp521",
-            },
+        let kind = match id {
+            "nistp256" => CurveKind::Nistp256,
+            "nistp384" => CurveKind::Nistp384,
+            "nistp521" => CurveKind::Nistp521,
+            "secp256k1" => CurveKind::Secp256k1,
             _ => return Err(Error::with_kind(ErrorKind::UnknownCurve(id.to_string()))),
         };
 
-        Ok(curve)
+        Ok(Curve::new(kind))
     }
 }
 
 /// ECDSA public key.
 /// The format of ECDSA public keys is described in RFC 5656, section 3.1.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EcdsaPublicKey {
     /// The curve being used.
     pub curve: Curve,
@@ -120,16 +590,232 @@ pub struct EcdsaPublicKey {
     pub key: Vec<u8>,
 }
 
+impl EcdsaPublicKey {
+    /// Returns the public key point, encoded as described in SEC 1, section 2.3.3.
+    pub fn point(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Returns the `x` and `y` coordinates of the uncompressed public key point.
+    ///
+    /// The size of each coordinate depends on the curve, e.g. the coordinates of
+    /// NIST P-521 points are 66 bytes long.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBIqJu+bvGfN31bSI7+EIEUp2wURByOwveazhoC+kqA4eKHS8Y1ehai2k0Z3+B+IPO+Hwh4dxUOsdRjfo1Ol2d2s= me@home").unwrap();
+    /// if let sshkeys::PublicKeyKind::Ecdsa(ref k) = key.kind {
+    ///     let (x, y) = k.coordinates().unwrap();
+    ///     assert_eq!(x.len(), 32);
+    ///     assert_eq!(y.len(), 32);
+    /// }
+    /// ```
+    pub fn coordinates(&self) -> Result<(&[u8], &[u8])> {
+        let len = coordinate_len(&self.curve);
+        if self.key.len() != 1 + 2 * len || self.key[0] != 0x04 {
+            return Err(Error::with_kind(ErrorKind::InvalidPoint));
+        }
+
+        Ok(self.key[1..].split_at(len))
+    }
+}
+
+#[cfg(feature = "verify")]
+impl EcdsaPublicKey {
+    /// Verifies an ECDSA signature of the given message.
+    ///
+    /// The signature is the ECDSA signature blob in the SSH wire format, i.e. the `r` and `s`
+    /// values encoded as `mpint`, as described in RFC 5656, section 3.1.2. The message is hashed
+    /// using SHA-256, SHA-384 or SHA-512, depending on the curve. Returns `Ok(false)` if the
+    /// signature does not match, and an error if the key or the signature is malformed.
+    ///
+    /// Only the NIST P-256, P-384 and P-521 curves are supported.
+    ///
+    /// This method is only available with the `verify` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example(key: &sshkeys::EcdsaPublicKey, message: &[u8], signature: &[u8]) -> sshkeys::Result<()> {
+    /// if key.verify(message, signature)? {
+    ///     println!("Valid signature");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        let mut reader = Reader::new(signature);
+        let r = reader.read_mpint()?;
+        let s = reader.read_mpint()?;
+
+        if reader.remaining() > 0 {
+            return Err(Error::with_kind(ErrorKind::TrailingData));
+        }
+
+        // The signature values are encoded as fixed size scalars, padded with zeros
+        let len = coordinate_len(&self.curve);
+        if r.len() > len || s.len() > len {
+            return Ok(false);
+        }
+
+        let mut rs = vec![0; 2 * len];
+        rs[len - r.len()..len].copy_from_slice(&r);
+        rs[2 * len - s.len()..].copy_from_slice(&s);
+
+        let verified = match self.curve.kind {
+            CurveKind::Nistp256 => {
+                let key = match p256::ecdsa::VerifyingKey::from_sec1_bytes(&self.key) {
+                    Ok(k) => k,
+                    Err(_) => return Err(Error::with_kind(ErrorKind::InvalidPoint)),
+                };
+
+                match p256::ecdsa::Signature::from_slice(&rs) {
+                    Ok(sig) => key.verify_prehash(&Sha256::digest(message), &sig).is_ok(),
+                    Err(_) => false,
+                }
+            }
+            CurveKind::Nistp384 => {
+                let key = match p384::ecdsa::VerifyingKey::from_sec1_bytes(&self.key) {
+                    Ok(k) => k,
+                    Err(_) => return Err(Error::with_kind(ErrorKind::InvalidPoint)),
+                };
+
+                match p384::ecdsa::Signature::from_slice(&rs) {
+                    Ok(sig) => key.verify_prehash(&Sha384::digest(message), &sig).is_ok(),
+                    Err(_) => false,
+                }
+            }
+            CurveKind::Nistp521 => {
+                let key = match p521::ecdsa::VerifyingKey::from_sec1_bytes(&self.key) {
+                    Ok(k) => k,
+                    Err(_) => return Err(Error::with_kind(ErrorKind::InvalidPoint)),
+                };
+
+                match p521::ecdsa::Signature::from_slice(&rs) {
+                    Ok(sig) => key.verify_prehash(&Sha512::digest(message), &sig).is_ok(),
+                    Err(_) => false,
+                }
+            }
+            CurveKind::Secp256k1 => {
+                return Err(Error::with_kind(ErrorKind::UnknownCurve(
+                    self.curve.identifier.to_string(),
+                )))
+            }
+        };
+
+        Ok(verified)
+    }
+}
+
 /// ED25519 public key.
 /// The format of ED25519 public keys is described in https://tools.ietf.org/html/draft-bjh21-ssh-ed25519-02
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Ed25519PublicKey {
     /// The public key.
     pub key: Vec<u8>,
 }
 
+#[cfg(feature = "verify")]
+impl Ed25519PublicKey {
+    /// Verifies an ED25519 signature of the given message.
+    ///
+    /// The signature is the raw 64 bytes ED25519 signature as described in RFC 8032,
+    /// and not the signature blob in the SSH wire format. Returns `Ok(false)` if
+    /// the signature does not match, and an error if the key or the signature is malformed.
+    ///
+    /// This method is only available with the `verify` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example(key: &sshkeys::Ed25519PublicKey, message: &[u8], signature: &[u8]) -> sshkeys::Result<()> {
+    /// if key.verify(message, signature)? {
+    ///     println!("Valid signature");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        let key = match VerifyingKey::try_from(self.key.as_slice()) {
+            Ok(k) => k,
+            Err(_) => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
+
+        let signature = match Signature::from_slice(signature) {
+            Ok(s) => s,
+            Err(_) => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
+
+        Ok(key.verify(message, &signature).is_ok())
+    }
+}
+
+/// ED448 public key.
+/// The format of ED448 public keys is described in RFC 8709, section 4.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ed448PublicKey {
+    /// The public key.
+    pub key: Vec<u8>,
+}
+
+/// ED25519 security key (FIDO/U2F) public key.
+/// The format of security key public keys is described in [PROTOCOL.u2f].
+/// [PROTOCOL.u2f]: https://cvsweb.openbsd.org/src/usr.bin/ssh/PROTOCOL.u2f?annotate=HEAD
+///
+/// The public portion of a security key consists only of the key and the application string.
+/// The flags, e.g. whether the key is resident, and the key handle are part of the private key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ed25519SkPublicKey {
+    /// The public key.
+    pub key: Vec<u8>,
+
+    /// The application string, usually `ssh:`.
+    pub application: String,
+}
+
+/// ECDSA security key (FIDO/U2F) public key.
+/// The format of security key public keys is described in [PROTOCOL.u2f].
+/// [PROTOCOL.u2f]: https://cvsweb.openbsd.org/src/usr.bin/ssh/PROTOCOL.u2f?annotate=HEAD
+///
+/// The public portion of a security key consists only of the key and the application string.
+/// The flags, e.g. whether the key is resident, and the key handle are part of the private key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EcdsaSkPublicKey {
+    /// The curve being used.
+    pub curve: Curve,
+
+    /// The public key.
+    pub key: Vec<u8>,
+
+    /// The application string, usually `ssh:`.
+    pub application: String,
+}
+
+/// XMSS public key.
+/// The format of XMSS public keys is described in [PROTOCOL.xmss] of the experimental
+/// XMSS support in OpenSSH.
+/// [PROTOCOL.xmss]: https://cvsweb.openbsd.org/src/usr.bin/ssh/PROTOCOL.xmss?annotate=HEAD
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmssPublicKey {
+    /// The name of the XMSS parameter set, e.g. `XMSS_SHA2_256_W16_H10`.
+    pub oid: String,
+
+    /// The public key.
+    pub key: Vec<u8>,
+}
+
 /// A type which represents an OpenSSH public key.
-#[derive(Debug, PartialEq)]
+///
+/// Equality and hashing of public keys is based on the encoded form of the
+/// key as returned by `PublicKey::encode`, which means that the comment is
+/// not taken into account. Two keys which differ only in their comment are
+/// therefore considered equal.
+///
+/// The `Debug` representation of a public key shows the key type, size and
+/// fingerprint instead of the raw key data. Use the `Debug` representation of
+/// `PublicKey::kind` to inspect the raw key data.
+#[derive(Clone)]
 pub struct PublicKey {
     /// Key type.
     pub key_type: KeyType,
@@ -139,58 +825,125 @@ pub struct PublicKey {
 
     /// Associated comment, if any.
     pub comment: Option<String>,
+
+    // The key blob the key was parsed from by `PublicKey::from_string_preserving`,
+    // if any, see `PublicKey::original_blob`.
+    pub(crate) original_blob: Option<OriginalBlob>,
 }
 
-impl fmt::Display for PublicKey {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let comment = match self.comment {
-            Some(ref c) => c,
-            None => "",
-        };
+// A key blob which was kept when parsing a key, along with the key type and kind it
+// was parsed as, which allow telling whether the key was modified since.
+#[derive(Clone)]
+pub(crate) struct OriginalBlob {
+    pub(crate) blob: Vec<u8>,
+    pub(crate) key_type: KeyType,
+    pub(crate) kind: PublicKeyKind,
+}
 
-        write!(
-            f,
-            "{} {} {}",
-            self.key_type,
-            base64::encode(&self.encode()),
-            comment
-        )
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &PublicKey) -> bool {
+        self.matches(other)
     }
 }
 
-/// The `FingerprintKind` enum represents the different fingerprint representation.
-#[derive(Debug, PartialEq)]
-pub enum FingerprintKind {
-    /// A kind used to represent the fingerprint using SHA256.
-    Sha256,
-
-    /// A kind used to represent the fingerprint using SHA384.
-    Sha384,
+impl Eq for PublicKey {}
 
-    /// A kind used to represent the fingerprint using SHA512.
-    Sha512,
+impl Hash for PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.encode().hash(state);
+    }
 }
 
-impl fmt::Display for FingerprintKind {
+impl fmt::Debug for PublicKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let kind = match *self {
-            FingerprintKind::Sha256 => "SHA256",
-            FingerprintKind::Sha384 => "SHA384",
-            FingerprintKind::Sha512 => "SHA512",
-        };
-
-        write!(f, "{}", kind)
+        f.debug_struct("PublicKey")
+            .field("key_type", &self.key_type.name)
+            .field("bits", &self.bits())
+            .field("fingerprint", &self.fingerprint().to_string())
+            .field("comment", &self.comment)
+            .finish()
     }
 }
 
-/// A type that represents an OpenSSH public key fingerprint.
-#[derive(Debug)]
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_key_string())
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = Error;
+
+    /// Reads a public key from a given string, the same way `PublicKey::from_string` does.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key: sshkeys::PublicKey = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home".parse().unwrap();
+    /// assert_eq!(key.key_type.name, "ssh-ed25519");
+    /// ```
+    fn from_str(s: &str) -> Result<PublicKey> {
+        PublicKey::from_string(s)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PublicKey {
+    type Error = Error;
+
+    /// Reads a public key from a given string, the same way `PublicKey::from_string` does.
+    fn try_from(s: &'a str) -> Result<PublicKey> {
+        PublicKey::from_string(s)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for PublicKey {
+    type Error = Error;
+
+    /// Reads a public key from a given byte sequence, the same way `PublicKey::from_bytes` does.
+    fn try_from(data: &'a [u8]) -> Result<PublicKey> {
+        PublicKey::from_bytes(data)
+    }
+}
+
+/// The `FingerprintKind` enum represents the different fingerprint representation.
+#[derive(Debug, PartialEq)]
+pub enum FingerprintKind {
+    /// A kind used to represent the fingerprint using SHA256.
+    Sha256,
+
+    /// A kind used to represent the fingerprint using SHA384.
+    Sha384,
+
+    /// A kind used to represent the fingerprint using SHA512.
+    Sha512,
+
+    /// A kind used to represent the legacy fingerprint using MD5.
+    Md5,
+}
+
+impl fmt::Display for FingerprintKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match *self {
+            FingerprintKind::Sha256 => "SHA256",
+            FingerprintKind::Sha384 => "SHA384",
+            FingerprintKind::Sha512 => "SHA512",
+            FingerprintKind::Md5 => "MD5",
+        };
+
+        write!(f, "{}", kind)
+    }
+}
+
+/// A type that represents an OpenSSH public key fingerprint.
+#[derive(Debug)]
 pub struct Fingerprint {
     /// The kind used to represent the fingerprint.
     pub kind: FingerprintKind,
 
-    /// The computed fingerprint.
-    pub hash: String,
+    // The encoded fingerprint and the raw digest it was encoded from,
+    // which are kept private so that they cannot diverge.
+    hash: String,
+    digest: Vec<u8>,
 }
 
 impl fmt::Display for Fingerprint {
@@ -205,37 +958,445 @@ impl Fingerprint {
     /// This method computes a fingerprint the way OpenSSH does it and is generally being
     /// used to compute the fingerprint of an already encoded OpenSSH public key.
     ///
+    /// The SHA fingerprints are base64 encoded without padding, while the legacy
+    /// MD5 fingerprint is represented as lowercase hex pairs separated by colons.
+    ///
     /// # Example
     /// ```rust
     /// # use sshkeys;
     /// let fp = sshkeys::Fingerprint::compute(sshkeys::FingerprintKind::Sha256, "some data".as_bytes());
     /// assert_eq!(fp.kind, sshkeys::FingerprintKind::Sha256);
-    /// assert_eq!(fp.hash, "EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6AkDe4");
+    /// assert_eq!(fp.hash(), "EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6AkDe4");
     /// ```
     pub fn compute<T: ?Sized + AsRef<[u8]>>(kind: FingerprintKind, data: &T) -> Fingerprint {
-        let digest = match kind {
-            FingerprintKind::Sha256 => Sha256::digest(&data.as_ref()).to_vec(),
-            FingerprintKind::Sha384 => Sha384::digest(&data.as_ref()).to_vec(),
-            FingerprintKind::Sha512 => Sha512::digest(&data.as_ref()).to_vec(),
+        Fingerprint::compute_with_config(kind, data, base64::STANDARD_NO_PAD)
+    }
+
+    /// Computes the fingerprint of a byte sequence using a given fingerprint representation
+    /// and a given base64 configuration for the SHA fingerprints.
+    ///
+    /// This is useful when the fingerprint is embedded in URLs or filenames, where the
+    /// `+` and `/` characters of the standard base64 alphabet are problematic.
+    /// The configuration does not affect the legacy MD5 fingerprint.
+    ///
+    /// `Fingerprint::compute` is equivalent to using the `base64::STANDARD_NO_PAD` configuration.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// # extern crate base64;
+    /// let fp = sshkeys::Fingerprint::compute_with_config(sshkeys::FingerprintKind::Sha256, "some data".as_bytes(), base64::URL_SAFE);
+    /// assert_eq!(fp.hash(), "EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6AkDe4=");
+    /// ```
+    pub fn compute_with_config<T: ?Sized + AsRef<[u8]>>(
+        kind: FingerprintKind,
+        data: &T,
+        config: base64::Config,
+    ) -> Fingerprint {
+        let digest = compute_digest(&kind, data.as_ref());
+        let hash = match kind {
+            FingerprintKind::Md5 => digest
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<String>>()
+                .join(":"),
+            _ => base64::encode_config(&digest, config),
+        };
+
+        Fingerprint { kind, hash, digest }
+    }
+
+    /// Returns the encoded fingerprint, e.g. the base64 encoded SHA-256 digest, without
+    /// the name of the fingerprint kind.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let fp = sshkeys::Fingerprint::compute(sshkeys::FingerprintKind::Sha256, "some data".as_bytes());
+    /// assert_eq!(fp.hash(), "EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6AkDe4");
+    /// ```
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Returns the raw digest bytes from which the fingerprint was computed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let fp = sshkeys::Fingerprint::compute(sshkeys::FingerprintKind::Sha256, "some data".as_bytes());
+    /// assert_eq!(fp.raw().len(), 32);
+    /// ```
+    pub fn raw(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// Compares the fingerprint with another fingerprint in constant time.
+    ///
+    /// Fingerprints of different kinds are never equal. Otherwise the raw digests are
+    /// compared in a way that does not depend on the position of the first
+    /// differing byte, which prevents leaking information through timing when
+    /// verifying a fingerprint against a pinned one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let fp = sshkeys::Fingerprint::compute(sshkeys::FingerprintKind::Sha256, "some data".as_bytes());
+    /// let expected: sshkeys::Fingerprint = "SHA256:EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6AkDe4".parse().unwrap();
+    /// assert!(fp.constant_time_eq(&expected));
+    /// ```
+    pub fn constant_time_eq(&self, other: &Fingerprint) -> bool {
+        if self.kind != other.kind || self.digest.len() != other.digest.len() {
+            return false;
+        }
+
+        let diff = self
+            .digest
+            .iter()
+            .zip(other.digest.iter())
+            .fold(0, |acc, (a, b)| acc | (a ^ b));
+
+        diff == 0
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = Error;
+
+    /// Parses a fingerprint from its string representation, e.g. `SHA256:...` or `MD5:...`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let fp: sshkeys::Fingerprint = "SHA256:EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6AkDe4".parse().unwrap();
+    /// assert_eq!(fp.kind, sshkeys::FingerprintKind::Sha256);
+    /// assert_eq!(fp.hash(), "EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6AkDe4");
+    /// ```
+    fn from_str(s: &str) -> Result<Fingerprint> {
+        let mut parts = s.splitn(2, ':');
+        let (prefix, hash) = match (parts.next(), parts.next()) {
+            (Some(p), Some(h)) => (p, h),
+            _ => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
         };
 
-        let mut encoded = base64::encode(&digest);
+        let (kind, size) = match prefix {
+            "SHA256" => (FingerprintKind::Sha256, 32),
+            "SHA384" => (FingerprintKind::Sha384, 48),
+            "SHA512" => (FingerprintKind::Sha512, 64),
+            "MD5" => (FingerprintKind::Md5, 16),
+            _ => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
 
-        // Trim padding characters from end
-        let hash = match encoded.find('=') {
-            Some(offset) => encoded.drain(..offset).collect(),
-            None => encoded,
+        let digest = match kind {
+            FingerprintKind::Md5 => {
+                let mut digest = Vec::new();
+                for pair in hash.split(':') {
+                    if pair.len() != 2 {
+                        return Err(Error::with_kind(ErrorKind::InvalidFormat));
+                    }
+                    match u8::from_str_radix(pair, 16) {
+                        Ok(b) => digest.push(b),
+                        Err(_) => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+                    }
+                }
+
+                digest
+            }
+            _ => base64::decode_config(hash, base64::STANDARD_NO_PAD)?,
         };
 
+        if digest.len() != size {
+            return Err(Error::with_kind(ErrorKind::InvalidFormat));
+        }
+
         let fp = Fingerprint {
-            kind: kind,
-            hash: hash,
+            kind,
+            hash: hash.to_string(),
+            digest,
+        };
+
+        Ok(fp)
+    }
+}
+
+// Ensures that no data is left in the reader after reading a public key.
+fn ensure_consumed(reader: &Reader) -> Result<()> {
+    if reader.remaining() > 0 {
+        return Err(Error::with_kind(ErrorKind::TrailingData));
+    }
+
+    Ok(())
+}
+
+// Splits the first non-blank line of an OpenSSH key string into the key type name,
+// the base64 encoded key data and the raw remainder of the line following the key data.
+// Blank and comment-only input is reported as `ErrorKind::EmptyInput`, while a comment
+// followed by further content is reported as `ErrorKind::InvalidFormat`.
+pub(crate) fn split_key_line(contents: &str) -> Result<(&str, &str, &str)> {
+    let mut lines = contents.trim_start().lines();
+    let line = lines.next().unwrap_or("");
+    if line.is_empty() {
+        return Err(Error::with_kind(ErrorKind::EmptyInput));
+    }
+
+    if line.starts_with('#') {
+        let kind = match lines
+            .map(str::trim_start)
+            .all(|l| l.is_empty() || l.starts_with('#'))
+        {
+            true => ErrorKind::EmptyInput,
+            false => ErrorKind::InvalidFormat,
         };
 
-        fp
+        return Err(Error::with_kind(kind));
+    }
+
+    let (kt_name, rest) = split_token(line);
+    let (data, rest) = split_token(rest.trim_start());
+
+    if kt_name.is_empty() || data.is_empty() {
+        return Err(Error::with_kind(ErrorKind::InvalidFormat));
+    }
+
+    Ok((kt_name, data, rest.trim_start()))
+}
+
+// The begin and end markers of a public key in the RFC 4716 SSH2 format.
+const RFC4716_BEGIN: &str = "---- BEGIN SSH2 PUBLIC KEY ----";
+const RFC4716_END: &str = "---- END SSH2 PUBLIC KEY ----";
+
+// The maximum length of RFC 4716 header lines and the length
+// the base64 encoded body is wrapped at.
+const RFC4716_HEADER_LEN: usize = 72;
+const RFC4716_LINE_LEN: usize = 70;
+
+// The length PuTTY wraps the base64 encoded body of RFC 4716 public keys at.
+const PUTTY_LINE_LEN: usize = 64;
+
+// The prefix of the first line of a PuTTY private key file, which is followed
+// by the version of the format and the name of the key type.
+const PPK_PREFIX: &str = "PuTTY-User-Key-File-";
+
+// Removes the double quotes surrounding a RFC 4716 header value, if any.
+fn unquote(value: &str) -> &str {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(v) => v,
+        None => value,
     }
 }
 
+// Splits a header line of a PuTTY private key file into the name and the value of the header.
+fn split_ppk_header(line: &str) -> Option<(&str, &str)> {
+    let mut parts = line.splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(value)) => Some((name.trim(), value.trim())),
+        _ => None,
+    }
+}
+
+// Reads the remaining lines of a key in the RFC 4716 or PuTTY format, which spans multiple
+// lines, and appends them to the given first line of the key. RFC 4716 keys end with the
+// end marker. PuTTY keys end with the `Private-MAC` header, or with the first line which
+// is not a header, if there are no private lines. That line is not part of the key and
+// is returned, so that it can be read as the next key.
+#[cfg(feature = "std")]
+fn read_key_block<R: BufRead>(reader: &mut R, block: &mut String) -> Result<Option<String>> {
+    let is_ppk = block.starts_with(PPK_PREFIX);
+    // Number of base64 encoded lines following the `Public-Lines` or `Private-Lines` header
+    let mut data_lines: usize = 0;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let trimmed = line.trim();
+
+        if !is_ppk {
+            let end = trimmed == RFC4716_END;
+            block.push_str(&line);
+            if end {
+                return Ok(None);
+            }
+            continue;
+        }
+
+        if data_lines > 0 {
+            data_lines -= 1;
+        } else {
+            match split_ppk_header(trimmed) {
+                Some(("Public-Lines", value)) | Some(("Private-Lines", value)) => {
+                    data_lines = value.parse().unwrap_or(0);
+                }
+                Some(("Private-MAC", _)) => {
+                    block.push_str(&line);
+                    return Ok(None);
+                }
+                Some((name, _)) if !name.is_empty() && !name.contains(char::is_whitespace) => {}
+                _ => return Ok(Some(line)),
+            }
+        }
+
+        block.push_str(&line);
+    }
+}
+
+// Converts the remainder of a key line into a comment, if there is one.
+pub(crate) fn parse_comment(rest: &str) -> Option<String> {
+    let comment = rest.trim();
+    if comment.is_empty() {
+        None
+    } else {
+        Some(comment.to_string())
+    }
+}
+
+// Splits a string at the first whitespace character.
+fn split_token(s: &str) -> (&str, &str) {
+    // Key lines are usually ASCII, for which looking at the bytes is much faster than
+    // decoding the characters. The vertical tab is whitespace, but not ASCII whitespace.
+    let offset = match s
+        .bytes()
+        .position(|b| b.is_ascii_whitespace() || b == 0x0b || !b.is_ascii())
+    {
+        Some(i) if s.as_bytes()[i].is_ascii() => Some(i),
+        Some(i) => s[i..].find(char::is_whitespace).map(|offset| i + offset),
+        None => None,
+    };
+
+    match offset {
+        Some(offset) => s.split_at(offset),
+        None => (s, ""),
+    }
+}
+
+// Computes the raw digest of a byte sequence using the hash
+// function associated with the given fingerprint kind.
+fn compute_digest(kind: &FingerprintKind, data: &[u8]) -> Vec<u8> {
+    match *kind {
+        FingerprintKind::Sha256 => Sha256::digest(data).to_vec(),
+        FingerprintKind::Sha384 => Sha384::digest(data).to_vec(),
+        FingerprintKind::Sha512 => Sha512::digest(data).to_vec(),
+        FingerprintKind::Md5 => Md5::digest(data).to_vec(),
+    }
+}
+
+// Vowels and consonants of the bubblebabble encoding.
+const BUBBLEBABBLE_VOWELS: &[u8] = b"aeiouy";
+const BUBBLEBABBLE_CONSONANTS: &[u8] = b"bcdfghklmnprstvzx";
+
+// Encodes a raw digest using the bubblebabble encoding by Antti Huima,
+// as implemented in OpenSSH's `fingerprint_bubblebabble()`.
+fn encode_bubblebabble(digest: &[u8]) -> String {
+    let vowel = |i: usize| BUBBLEBABBLE_VOWELS[i] as char;
+    let consonant = |i: usize| BUBBLEBABBLE_CONSONANTS[i] as char;
+
+    let rounds = digest.len() / 2 + 1;
+    let mut seed = 1;
+    let mut result = String::from("x");
+
+    for i in 0..rounds {
+        if i + 1 < rounds || !digest.len().is_multiple_of(2) {
+            let byte1 = digest[2 * i] as usize;
+            result.push(vowel((((byte1 >> 6) & 3) + seed) % 6));
+            result.push(consonant((byte1 >> 2) & 15));
+            result.push(vowel(((byte1 & 3) + seed / 6) % 6));
+
+            if i + 1 < rounds {
+                let byte2 = digest[2 * i + 1] as usize;
+                result.push(consonant((byte2 >> 4) & 15));
+                result.push('-');
+                result.push(consonant(byte2 & 15));
+
+                // The checksum is carried over to the next round
+                seed = (seed * 5 + byte1 * 7 + byte2) % 36;
+            }
+        } else {
+            // The final round encodes only the checksum
+            result.push(vowel(seed % 6));
+            result.push(consonant(16));
+            result.push(vowel(seed / 6));
+        }
+    }
+
+    result.push('x');
+    result
+}
+
+// Dimensions of the randomart field, as used by OpenSSH.
+const RANDOMART_WIDTH: usize = 17;
+const RANDOMART_HEIGHT: usize = 9;
+
+// Symbols used to represent how many times the bishop visited a given position.
+// The last two symbols mark the start and end positions of the walk.
+const RANDOMART_SYMBOLS: &[u8] = b" .o+=*BOX@%&#/^SE";
+
+// Renders the randomart of a raw digest using the "drunken bishop" algorithm,
+// as described in http://www.dirk-loss.de/sshvis/drunken_bishop.pdf and
+// implemented in OpenSSH's `sshkey_fingerprint_randomart()`.
+fn render_randomart(digest: &[u8], title: &str, footer: &str) -> String {
+    let mut field = [[0usize; RANDOMART_HEIGHT]; RANDOMART_WIDTH];
+    let max = RANDOMART_SYMBOLS.len() - 1;
+
+    // The bishop starts in the middle of the field
+    let mut x = RANDOMART_WIDTH / 2;
+    let mut y = RANDOMART_HEIGHT / 2;
+
+    // Each byte of the digest provides four moves, two bits each,
+    // starting with the least significant bits.
+    for byte in digest {
+        let mut input = *byte;
+        for _ in 0..4 {
+            x = if input & 0x1 != 0 {
+                (x + 1).min(RANDOMART_WIDTH - 1)
+            } else {
+                x.saturating_sub(1)
+            };
+            y = if input & 0x2 != 0 {
+                (y + 1).min(RANDOMART_HEIGHT - 1)
+            } else {
+                y.saturating_sub(1)
+            };
+
+            if field[x][y] < max - 2 {
+                field[x][y] += 1;
+            }
+
+            input >>= 2;
+        }
+    }
+
+    // Mark the start and end positions
+    field[RANDOMART_WIDTH / 2][RANDOMART_HEIGHT / 2] = max - 1;
+    field[x][y] = max;
+
+    let mut art = String::new();
+    art.push_str(&randomart_border(title));
+    art.push('\n');
+
+    for y in 0..RANDOMART_HEIGHT {
+        art.push('|');
+        for column in field.iter() {
+            art.push(RANDOMART_SYMBOLS[column[y].min(max)] as char);
+        }
+        art.push('|');
+        art.push('\n');
+    }
+
+    art.push_str(&randomart_border(footer));
+
+    art
+}
+
+// Renders a randomart border line with the given label centered in it.
+fn randomart_border(label: &str) -> String {
+    let label = &label[..label.len().min(RANDOMART_WIDTH)];
+    let left = (RANDOMART_WIDTH - label.len()) / 2;
+    let right = RANDOMART_WIDTH - label.len() - left;
+
+    format!("+{}{}{}+", "-".repeat(left), label, "-".repeat(right))
+}
+
 impl PublicKey {
     /// Reads an OpenSSH public key from a given path.
     ///
@@ -247,178 +1408,1098 @@ impl PublicKey {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "std")]
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<PublicKey> {
+        PublicKey::from_reader_io(File::open(path)?)
+    }
+
+    /// Reads an OpenSSH public key from a given reader.
+    ///
+    /// The reader is consumed until EOF is reached.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home\n";
+    /// let key = sshkeys::PublicKey::from_reader_io(data.as_bytes()).unwrap();
+    /// assert_eq!(key.comment, Some("me@home".to_string()));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_reader_io<R: Read>(mut reader: R) -> Result<PublicKey> {
         let mut contents = String::new();
-        File::open(path)?.read_to_string(&mut contents)?;
+        reader.read_to_string(&mut contents)?;
+
+        PublicKey::from_string(&contents)
+    }
+
+    /// Reads an OpenSSH public key from the standard input.
+    ///
+    /// The standard input is consumed until EOF is reached, the same way as by
+    /// `PublicKey::from_reader_io`. Leading blank lines are skipped and only the first
+    /// key line is parsed, so a trailing newline or any further lines are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let key = sshkeys::PublicKey::from_stdin()?;
+    /// println!("{}", key.fingerprint());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_stdin() -> Result<PublicKey> {
+        PublicKey::from_reader_io(io::stdin())
+    }
+
+    /// Returns an iterator over the OpenSSH public keys read from a given reader,
+    /// which yields one parsed key per line.
+    ///
+    /// Lines are read lazily, so that callers can stop at the first error.
+    /// Blank lines and lines starting with `#` are skipped. Keys in the RFC 4716
+    /// and PuTTY formats, which span multiple lines, are read as a whole and
+    /// yield a single key.
+    ///
+    /// The buffers for reading the lines and decoding the key data are reused across
+    /// lines, which makes this faster than reading each line from the reader and
+    /// parsing it with `PublicKey::from_string`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = "# my keys
+    /// ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home
+    ///
+    /// ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@work
+    /// ";
+    /// let keys = sshkeys::PublicKey::iter_from_reader(data.as_bytes())
+    ///     .collect::<sshkeys::Result<Vec<_>>>()
+    ///     .unwrap();
+    /// assert_eq!(keys.len(), 2);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn iter_from_reader<R: Read>(reader: R) -> impl Iterator<Item = Result<PublicKey>> {
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        let mut buf = Vec::new();
+        // The line following a PuTTY key, which was read while looking for its end
+        let mut pending: Option<String> = None;
+
+        iter::from_fn(move || loop {
+            match pending.take() {
+                Some(l) => line = l,
+                None => {
+                    line.clear();
+                    match reader.read_line(&mut line) {
+                        Ok(0) => return None,
+                        Ok(_) => {}
+                        Err(e) => return Some(Err(e.into())),
+                    }
+                }
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            if trimmed.starts_with(RFC4716_BEGIN) || trimmed.starts_with(PPK_PREFIX) {
+                let mut block = format!("{}\n", trimmed);
+                pending = match read_key_block(&mut reader, &mut block) {
+                    Ok(v) => v,
+                    Err(e) => return Some(Err(e)),
+                };
+
+                return Some(PublicKey::from_string(&block));
+            }
+
+            return Some(PublicKey::from_key_line(trimmed, &mut buf).map(|(key, _)| key));
+        })
+    }
+
+    /// Reads an OpenSSH public key from a given string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// let fp = key.fingerprint();
+    /// assert_eq!(fp.hash(), "ciQkdxjFUhk2E2vRkWJD9kB8pi+EneOkaCJJHNWzPC4");
+    /// ```
+    pub fn from_string(contents: &str) -> Result<PublicKey> {
+        if contents.trim_start().starts_with(RFC4716_BEGIN) {
+            return PublicKey::from_rfc4716(contents);
+        }
+
+        if contents.trim_start().starts_with(PPK_PREFIX) {
+            return PublicKey::from_ppk(contents);
+        }
+
+        PublicKey::from_string_with_remainder(contents).map(|(key, _)| key)
+    }
+
+    /// Reads an OpenSSH public key from a given string the same way as
+    /// `PublicKey::from_string`, but keeps the key blob it was parsed from.
+    ///
+    /// Parsing normalizes the key, e.g. redundant leading zero bytes of `mpint` values
+    /// are dropped, so re-encoding the key may give a different key blob. The key blob
+    /// which was kept is used instead when writing the key, e.g. by
+    /// `PublicKey::to_key_string`, so that the key is re-emitted exactly as it was read,
+    /// as long as the key is not modified. See `PublicKey::original_blob`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// // The exponent of the key has a redundant leading zero byte
+    /// let line = "ssh-rsa AAAAB3NzaC1yc2EAAAAEAAEAAQAAAAMAxRs= me@home";
+    /// let key = sshkeys::PublicKey::from_string(line).unwrap();
+    /// assert_eq!(key.to_key_string(), "ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAAAAwDFGw== me@home");
+    ///
+    /// let key = sshkeys::PublicKey::from_string_preserving(line).unwrap();
+    /// assert_eq!(key.to_key_string(), line);
+    /// ```
+    pub fn from_string_preserving(contents: &str) -> Result<PublicKey> {
+        if contents.trim_start().starts_with(RFC4716_BEGIN) {
+            return PublicKey::parse_rfc4716(contents, true);
+        }
+
+        if contents.trim_start().starts_with(PPK_PREFIX) {
+            return PublicKey::parse_ppk(contents, true);
+        }
+
+        let mut buf = Vec::new();
+        let (mut key, _) = PublicKey::from_key_line(contents, &mut buf)?;
+        key.preserve_blob(buf);
+
+        Ok(key)
+    }
+
+    /// Reads a public key in the RFC 4716 SSH2 format from a given string.
+    ///
+    /// The body of the key may be wrapped across multiple lines and the value
+    /// of the `Comment` header, if any, is used as the comment of the key.
+    /// All other headers are ignored. `PublicKey::from_string` detects this format
+    /// automatically, so this method only needs to be called directly when the
+    /// input is known to be in RFC 4716 format.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = "---- BEGIN SSH2 PUBLIC KEY ----
+    /// Comment: \"me@home\"
+    /// AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpU
+    /// yOAPrzPLy4czEZw/sh8m8rd
+    /// ---- END SSH2 PUBLIC KEY ----
+    /// ";
+    /// let key = sshkeys::PublicKey::from_rfc4716(data).unwrap();
+    /// assert_eq!(key.comment, Some("me@home".to_string()));
+    /// ```
+    pub fn from_rfc4716(contents: &str) -> Result<PublicKey> {
+        PublicKey::parse_rfc4716(contents, false)
+    }
+
+    // Reads a public key in the RFC 4716 SSH2 format, keeping the key blob if requested.
+    fn parse_rfc4716(contents: &str, preserve: bool) -> Result<PublicKey> {
+        // Only trailing whitespace is removed from the lines, as leading whitespace
+        // of continued header lines is part of the header value.
+        let mut lines = contents
+            .lines()
+            .map(str::trim_end)
+            .filter(|l| !l.is_empty());
+        if lines.next().map(str::trim_start) != Some(RFC4716_BEGIN) {
+            return Err(Error::with_kind(ErrorKind::InvalidFormat));
+        }
+
+        let mut comment = None;
+        let mut header = String::new();
+        let mut body = String::new();
+
+        loop {
+            let line = match lines.next() {
+                Some(l) if l.trim_start() == RFC4716_END => break,
+                Some(l) => l,
+                None => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+            };
+
+            // Header lines precede the body and may be continued on the next
+            // line by ending them with a backslash character.
+            if body.is_empty() && (!header.is_empty() || line.contains(':')) {
+                if let Some(v) = line.strip_suffix('\\') {
+                    header.push_str(v);
+                    continue;
+                }
+                header.push_str(line);
+
+                let mut parts = header.splitn(2, ':');
+                if let (Some(tag), Some(value)) = (parts.next(), parts.next()) {
+                    if tag.trim().eq_ignore_ascii_case("Comment") {
+                        comment = parse_comment(unquote(value.trim()));
+                    }
+                }
+                header.clear();
+            } else {
+                body.push_str(line.trim_start());
+            }
+        }
+
+        let decoded = base64::decode(&body)?;
+        let mut key = PublicKey::from_blob(decoded, preserve)?;
+        key.comment = comment;
+
+        Ok(key)
+    }
+
+    /// Reads the public key from the contents of a PuTTY `.ppk` private key file.
+    ///
+    /// Versions 2 and 3 of the format are supported. The public key is read from the
+    /// base64 encoded lines following the `Public-Lines` header and the value of the
+    /// `Comment` header, if any, is used as the comment of the key. The private key
+    /// is not read, so encrypted files are supported as well. `PublicKey::from_string`
+    /// detects this format automatically.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = "PuTTY-User-Key-File-2: ssh-ed25519
+    /// Encryption: none
+    /// Comment: me@home
+    /// Public-Lines: 2
+    /// AAAAC3NzaC1lZDI1NTE5AAAAIMIVp6q5co/r5GwY0dH+NYQbfKicapeF3gXEU3dz
+    /// aAvD
+    /// ";
+    /// let key = sshkeys::PublicKey::from_ppk(data).unwrap();
+    /// assert_eq!(key.key_type.name, "ssh-ed25519");
+    /// assert_eq!(key.comment, Some("me@home".to_string()));
+    /// ```
+    pub fn from_ppk(contents: &str) -> Result<PublicKey> {
+        PublicKey::parse_ppk(contents, false)
+    }
+
+    // Reads the public key of a PuTTY private key file, keeping the key blob if requested.
+    fn parse_ppk(contents: &str, preserve: bool) -> Result<PublicKey> {
+        let mut lines = contents.lines().map(str::trim).filter(|l| !l.is_empty());
+
+        let kt_name = match lines.next().and_then(|l| l.strip_prefix(PPK_PREFIX)) {
+            Some(v) => match split_ppk_header(v) {
+                Some(("2", name)) | Some(("3", name)) => name,
+                _ => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+            },
+            None => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
+
+        let mut comment = None;
+        let mut body = None;
+
+        while let Some(line) = lines.next() {
+            match split_ppk_header(line) {
+                Some(("Comment", value)) => comment = parse_comment(value),
+                Some(("Public-Lines", value)) => {
+                    let count: usize = match value.parse() {
+                        Ok(v) => v,
+                        Err(_) => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+                    };
+
+                    let mut data = String::new();
+                    for _ in 0..count {
+                        match lines.next() {
+                            Some(l) => data.push_str(l),
+                            None => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+                        }
+                    }
+                    body = Some(data);
+                }
+                // The private key follows the public key, so there is nothing left to read.
+                Some(("Private-Lines", _)) => break,
+                Some(_) => {}
+                None => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+            }
+        }
+
+        let body = match body {
+            Some(v) => v,
+            None => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
+
+        let decoded = base64::decode(&body)?;
+        let mut key = PublicKey::from_blob(decoded, preserve)?;
+        if key.key_type.name != kt_name {
+            return Err(Error::with_kind(ErrorKind::KeyTypeMismatch));
+        }
+        key.comment = comment;
+
+        Ok(key)
+    }
+
+    /// Reads an OpenSSH public key from a given string and returns it along with
+    /// the raw remainder of the line following the base64 encoded key data.
+    ///
+    /// The whitespace separating the key data from the remainder is not included.
+    /// The comment of the returned key is the trimmed remainder, if it is not empty,
+    /// which allows comments with spaces in them to be preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let (key, rest) = sshkeys::PublicKey::from_string_with_remainder("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home my laptop").unwrap();
+    /// assert_eq!(rest, "me@home my laptop");
+    /// assert_eq!(key.comment, Some("me@home my laptop".to_string()));
+    /// ```
+    pub fn from_string_with_remainder(contents: &str) -> Result<(PublicKey, &str)> {
+        let mut buf = Vec::new();
+
+        PublicKey::from_key_line(contents, &mut buf)
+    }
+
+    /// Reads only the key type of an OpenSSH public key from a given string.
+    ///
+    /// The key type name is validated against the key type at the beginning of the key blob,
+    /// the same way as by `PublicKey::from_string`, but only the part of the base64 encoded
+    /// key data which contains the key type is decoded. This is faster than parsing the key
+    /// when only the key type is needed, e.g. for counting the keys of a file by type.
+    /// The rest of the key data is not validated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let kt = sshkeys::PublicKey::peek_key_type("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert_eq!(kt.kind, sshkeys::KeyTypeKind::Ed25519);
+    /// ```
+    pub fn peek_key_type(contents: &str) -> Result<KeyType> {
+        let (kt_name, data, _) = split_key_line(contents)?;
+        let kt = KeyType::from_token(kt_name)?;
+        let data = data.as_bytes();
+
+        // The length of the key type is encoded in the first 8 characters
+        let head = base64::decode(&data[..data.len().min(8)])?;
+        let len = Reader::new(&head).read_u32()? as usize;
+
+        // Each group of 3 bytes is encoded as 4 characters
+        let encoded_len = (len.saturating_add(4).saturating_add(2) / 3).saturating_mul(4);
+        let decoded = base64::decode(&data[..data.len().min(encoded_len)])?;
+
+        let kt_from_reader = Reader::new(&decoded).read_str()?;
+        if kt.name != kt_from_reader {
+            return Err(Error::with_kind(ErrorKind::KeyTypeMismatch));
+        }
+
+        Ok(kt)
+    }
+
+    // Reads an OpenSSH public key from a given string the same way as
+    // `PublicKey::from_string_with_remainder`, decoding the key blob into the given buffer,
+    // which allows the buffer to be reused when reading many keys.
+    fn from_key_line<'a>(contents: &'a str, buf: &mut Vec<u8>) -> Result<(PublicKey, &'a str)> {
+        let (kt_name, data, rest) = split_key_line(contents)?;
+        let kt = KeyType::from_token(kt_name)?;
+
+        buf.clear();
+        base64::decode_config_buf(data, base64::STANDARD, buf)?;
+        let mut reader = Reader::new(buf);
+
+        // Validate key type before reading rest of the data
+        let kt_from_reader = reader.read_str()?;
+        if kt.name != kt_from_reader {
+            return Err(Error::with_kind(ErrorKind::KeyTypeMismatch));
+        }
+
+        // Construct a new `PublicKey` value and preserve the `comment` value.
+        let k = PublicKey::from_reader(kt.name, &mut reader)?;
+        ensure_consumed(&reader)?;
+        let key = PublicKey {
+            key_type: kt,
+            kind: k.kind,
+            comment: parse_comment(rest),
+            original_blob: None,
+        };
+
+        Ok((key, rest))
+    }
+
+    /// Reads a public key from a given byte sequence.
+    ///
+    /// The byte sequence is expected to be the base64 decoded body of the public key.
+    /// Any data left after the public key results in an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 11, 115, 115, 104, 45,
+    ///                 101, 100, 50, 53, 53, 49, 57,
+    ///                 0, 0, 0, 32, 121, 27, 123, 184,
+    ///                 48, 199, 187, 52, 118, 80, 41, 16,
+    ///                 76, 233, 83, 35, 128, 62, 188,
+    ///                 207, 47, 46, 28, 204, 70, 112,
+    ///                 254, 200, 124, 155, 202, 221];
+    ///
+    /// let key = sshkeys::PublicKey::from_bytes(&data).unwrap();
+    /// let fp = key.fingerprint();
+    /// assert_eq!(fp.hash(), "ciQkdxjFUhk2E2vRkWJD9kB8pi+EneOkaCJJHNWzPC4");
+    /// ```
+    pub fn from_bytes<T: ?Sized + AsRef<[u8]>>(data: &T) -> Result<PublicKey> {
+        let mut reader = Reader::new(&data);
+        let kt_name = reader.read_string()?;
+        let key = PublicKey::from_reader(&kt_name, &mut reader)?;
+        ensure_consumed(&reader)?;
+
+        Ok(key)
+    }
+
+    // Reads a public key from a decoded key blob, which is kept if requested.
+    fn from_blob(blob: Vec<u8>, preserve: bool) -> Result<PublicKey> {
+        let mut key = PublicKey::from_bytes(&blob)?;
+        if preserve {
+            key.preserve_blob(blob);
+        }
+
+        Ok(key)
+    }
+
+    // Keeps the key blob the key was parsed from, see `PublicKey::original_blob`.
+    fn preserve_blob(&mut self, blob: Vec<u8>) {
+        self.original_blob = Some(OriginalBlob {
+            blob,
+            key_type: self.key_type.clone(),
+            kind: self.kind.clone(),
+        });
+    }
+
+    /// Creates a new public key from the given key type, kind and comment.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::new(
+    ///     sshkeys::KeyType::from_name("ssh-ed25519").unwrap(),
+    ///     sshkeys::PublicKeyKind::Ed25519(sshkeys::Ed25519PublicKey { key: vec![0; 32] }),
+    ///     Some("me@home".to_string()),
+    /// );
+    /// assert_eq!(key.bits(), 256);
+    /// ```
+    pub fn new(key_type: KeyType, kind: PublicKeyKind, comment: Option<String>) -> PublicKey {
+        PublicKey {
+            key_type,
+            kind,
+            comment,
+            original_blob: None,
+        }
+    }
+
+    /// Creates a new RSA public key from the given exponent and modulus.
+    ///
+    /// The parameters are big-endian numbers, any leading zero bytes are removed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_rsa(vec![0, 1, 0, 1], vec![0, 0xc5, 0x1b]);
+    /// assert_eq!(key.key_type.name, "ssh-rsa");
+    /// assert_eq!(key.kind, sshkeys::PublicKeyKind::Rsa(sshkeys::RsaPublicKey {
+    ///     e: vec![1, 0, 1],
+    ///     n: vec![0xc5, 0x1b],
+    /// }));
+    /// ```
+    pub fn from_rsa(e: Vec<u8>, n: Vec<u8>) -> PublicKey {
+        let k = RsaPublicKey {
+            e: strip_leading_zeros(&e).to_vec(),
+            n: strip_leading_zeros(&n).to_vec(),
+        };
+
+        PublicKey::from_kind("ssh-rsa", PublicKeyKind::Rsa(k))
+    }
+
+    /// Creates a new DSA public key from the given parameters.
+    ///
+    /// The parameters are big-endian numbers, any leading zero bytes are removed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_dsa(vec![0, 0xb1], vec![0x8f], vec![0x5a], vec![0x13]);
+    /// assert_eq!(key.key_type.name, "ssh-dss");
+    /// ```
+    pub fn from_dsa(p: Vec<u8>, q: Vec<u8>, g: Vec<u8>, y: Vec<u8>) -> PublicKey {
+        let k = DsaPublicKey {
+            p: strip_leading_zeros(&p).to_vec(),
+            q: strip_leading_zeros(&q).to_vec(),
+            g: strip_leading_zeros(&g).to_vec(),
+            y: strip_leading_zeros(&y).to_vec(),
+        };
+
+        PublicKey::from_kind("ssh-dss", PublicKeyKind::Dsa(k))
+    }
+
+    /// Creates a new ECDSA public key from the given curve and encoded curve point.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let curve = sshkeys::Curve::from_identifier("nistp384").unwrap();
+    /// let key = sshkeys::PublicKey::from_ecdsa(curve, vec![4; 97]);
+    /// assert_eq!(key.key_type.name, "ecdsa-sha2-nistp384");
+    /// ```
+    pub fn from_ecdsa(curve: Curve, key: Vec<u8>) -> PublicKey {
+        let name = match curve.kind {
+            CurveKind::Nistp256 => "ecdsa-sha2-nistp256",
+            CurveKind::Nistp384 => "ecdsa-sha2-nistp384",
+            CurveKind::Nistp521 => "ecdsa-sha2-nistp521",
+            CurveKind::Secp256k1 => "ecdsa-sha2-secp256k1",
+        };
+
+        PublicKey::from_kind(name, PublicKeyKind::Ecdsa(EcdsaPublicKey { curve, key }))
+    }
+
+    /// Creates a new ED25519 public key from the given key bytes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_ed25519(vec![0; 32]);
+    /// assert_eq!(key.key_type.name, "ssh-ed25519");
+    /// ```
+    pub fn from_ed25519(key: Vec<u8>) -> PublicKey {
+        PublicKey::from_kind(
+            "ssh-ed25519",
+            PublicKeyKind::Ed25519(Ed25519PublicKey { key }),
+        )
+    }
+
+    /// Creates a new ED448 public key from the given key bytes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_ed448(vec![0; 57]);
+    /// assert_eq!(key.key_type.name, "ssh-ed448");
+    /// ```
+    pub fn from_ed448(key: Vec<u8>) -> PublicKey {
+        PublicKey::from_kind("ssh-ed448", PublicKeyKind::Ed448(Ed448PublicKey { key }))
+    }
+
+    // Creates a new public key without a comment from the given kind.
+    // The key type name must be one of the names known to `KeyType::from_name`.
+    fn from_kind(kt_name: &str, kind: PublicKeyKind) -> PublicKey {
+        PublicKey {
+            key_type: KeyType::from_name(kt_name).expect("known key type"),
+            kind,
+            comment: None,
+            original_blob: None,
+        }
+    }
+
+    // This function is used for extracting a public key from an existing reader, e.g.
+    // we already have a reader for reading an OpenSSH certificate key and
+    // we want to extract the public key information from it.
+    pub(crate) fn from_reader(kt_name: &str, reader: &mut Reader) -> Result<PublicKey> {
+        let kt = KeyType::from_exact_name(kt_name)?;
+
+        let kind = match kt.kind {
+            KeyTypeKind::Rsa | KeyTypeKind::RsaCert => {
+                let e = reader.read_mpint()?;
+
+                // The size of the modulus is checked before it is copied
+                let n = reader.read_mpint_ref()?;
+                let bits = mpint_bits(n);
+                if bits > RSA_MAX_BITS {
+                    return Err(Error::with_kind(ErrorKind::KeyTooLarge {
+                        bits,
+                        max: RSA_MAX_BITS,
+                    }));
+                }
+
+                let k = RsaPublicKey { e, n: n.to_vec() };
+
+                PublicKeyKind::Rsa(k)
+            }
+            KeyTypeKind::Dsa | KeyTypeKind::DsaCert => {
+                let k = DsaPublicKey {
+                    p: reader.read_mpint()?,
+                    q: reader.read_mpint()?,
+                    g: reader.read_mpint()?,
+                    y: reader.read_mpint()?,
+                };
+
+                PublicKeyKind::Dsa(k)
+            }
+            KeyTypeKind::Ecdsa | KeyTypeKind::EcdsaCert => {
+                let curve = read_curve(&kt, reader)?;
+                let key = read_point(&curve, reader)?;
+                let k = EcdsaPublicKey { curve, key };
+
+                PublicKeyKind::Ecdsa(k)
+            }
+            KeyTypeKind::Ed25519 | KeyTypeKind::Ed25519Cert => {
+                let k = Ed25519PublicKey {
+                    key: reader.read_bytes()?,
+                };
+
+                PublicKeyKind::Ed25519(k)
+            }
+            KeyTypeKind::Ed448 => {
+                let k = Ed448PublicKey {
+                    key: reader.read_bytes()?,
+                };
+
+                PublicKeyKind::Ed448(k)
+            }
+            KeyTypeKind::SkEd25519 | KeyTypeKind::SkEd25519Cert => {
+                let k = Ed25519SkPublicKey {
+                    key: reader.read_bytes()?,
+                    application: reader.read_string()?,
+                };
+
+                PublicKeyKind::SkEd25519(k)
+            }
+            KeyTypeKind::SkEcdsa | KeyTypeKind::SkEcdsaCert => {
+                let curve = read_curve(&kt, reader)?;
+                let key = read_point(&curve, reader)?;
+                let k = EcdsaSkPublicKey {
+                    curve,
+                    key,
+                    application: reader.read_string()?,
+                };
+
+                PublicKeyKind::SkEcdsa(k)
+            }
+            KeyTypeKind::Xmss => {
+                let k = XmssPublicKey {
+                    oid: reader.read_string()?,
+                    key: reader.read_bytes()?,
+                };
+
+                PublicKeyKind::Xmss(k)
+            }
+        };
+
+        let key = PublicKey {
+            key_type: kt,
+            kind,
+            comment: None,
+            original_blob: None,
+        };
+
+        Ok(key)
+    }
+
+    /// Returns the algorithm name of the public key, e.g. `ssh-ed25519`.
+    ///
+    /// For certificate key types this is the name of the plain algorithm,
+    /// without the certificate suffix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert_eq!(key.algorithm(), "ssh-ed25519");
+    /// ```
+    pub fn algorithm(&self) -> &str {
+        self.key_type.plain
+    }
+
+    /// Returns the public key with the given comment, replacing the existing comment, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// let key = key.with_comment(Some("me@work".to_string()));
+    /// assert_eq!(key.to_key_string(), "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@work");
+    /// ```
+    pub fn with_comment(mut self, comment: Option<String>) -> PublicKey {
+        self.set_comment(comment);
+        self
+    }
+
+    /// Sets the comment of the public key, or clears it if the comment is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let mut key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// key.set_comment(None);
+    /// assert_eq!(key.to_key_string(), "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd");
+    /// ```
+    pub fn set_comment(&mut self, comment: Option<String>) {
+        self.comment = comment;
+    }
+
+    /// Returns `true` if the public key is part of a certificate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert!(!key.is_certificate());
+    /// ```
+    pub fn is_certificate(&self) -> bool {
+        self.key_type.is_cert()
+    }
+
+    /// Returns the number of bits of the public key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert_eq!(key.bits(), 256);
+    /// ```
+    pub fn bits(&self) -> usize {
+        match self.kind {
+            // For RSA public key the size of the key is the number of bits of the modulus
+            PublicKeyKind::Rsa(ref k) => mpint_bits(&k.n),
+            // For DSA public keys the size of the key is the number of bits of the `p` parameter
+            PublicKeyKind::Dsa(ref k) => mpint_bits(&k.p),
+            // ECDSA key size depends on the curve
+            PublicKeyKind::Ecdsa(ref k) => match k.curve.kind {
+                CurveKind::Nistp256 | CurveKind::Secp256k1 => 256,
+                CurveKind::Nistp384 => 384,
+                CurveKind::Nistp521 => 521,
+            },
+            // ED25519 key size is 256 bits
+            // https://tools.ietf.org/html/draft-josefsson-eddsa-ed25519-03#section-5.5
+            PublicKeyKind::Ed25519(_) | PublicKeyKind::SkEd25519(_) => 256,
+            // ED448 key size is 448 bits, RFC 8032, section 5.2
+            PublicKeyKind::Ed448(_) => 448,
+            // Security keys support only the NIST P-256 curve
+            PublicKeyKind::SkEcdsa(_) => 256,
+            // XMSS keys are reported as 256 bits, the same way OpenSSH does
+            PublicKeyKind::Xmss(_) => 256,
+        }
+    }
+
+    /// Returns the approximate security strength of the key in bits, i.e. the size of the
+    /// key of a symmetric cipher which is estimated to be as hard to break as the key.
+    ///
+    /// Unlike `PublicKey::bits`, this allows comparing the strength of different kinds of keys.
+    /// The strength of RSA and DSA keys is estimated by NIST SP 800-57 Part 1 from the size of
+    /// the modulus, or of the prime `p` for DSA keys, as follows.
+    ///
+    /// | Modulus size | Security strength |
+    /// |--------------|-------------------|
+    /// | < 1024       | 0                 |
+    /// | 1024 - 2047  | 80                |
+    /// | 2048 - 3071  | 112               |
+    /// | 3072 - 7679  | 128               |
+    /// | 7680 - 15359 | 192               |
+    /// | >= 15360     | 256               |
+    ///
+    /// The strength of DSA keys is further limited to half the size of the subprime `q`.
+    /// ECDSA, ED25519 and ED448 keys provide half the size of the curve, i.e. 128 bits for
+    /// the P-256, secp256k1 and ED25519 curves, 192 bits for P-384, 256 bits for P-521 and
+    /// 224 bits for ED448. XMSS keys provide the size of the hash function output in bits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert_eq!(key.security_bits(), 128);
+    /// ```
+    pub fn security_bits(&self) -> u32 {
+        match self.kind {
+            PublicKeyKind::Rsa(ref k) => modulus_security_bits(mpint_bits(&k.n)),
+            PublicKeyKind::Dsa(ref k) => {
+                let q_bits = (mpint_bits(&k.q) / 2) as u32;
+                modulus_security_bits(mpint_bits(&k.p)).min(q_bits)
+            }
+            PublicKeyKind::Ecdsa(_)
+            | PublicKeyKind::SkEcdsa(_)
+            | PublicKeyKind::Ed25519(_)
+            | PublicKeyKind::SkEd25519(_)
+            | PublicKeyKind::Ed448(_) => match self.curve() {
+                // The size of the P-521 curve is rounded down, the same way as NIST does
+                Some(CurveKind::Nistp521) => 256,
+                _ => (self.bits() / 2) as u32,
+            },
+            // The public key consists of the root node and the public seed, which both
+            // are of the size of the hash function output
+            PublicKeyKind::Xmss(ref k) => (k.key.len() / 2 * 8) as u32,
+        }
+    }
+
+    /// Returns the curve of ECDSA keys, including ECDSA security keys, and `None`
+    /// for all other kinds of keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBIqJu+bvGfN31bSI7+EIEUp2wURByOwveazhoC+kqA4eKHS8Y1ehai2k0Z3+B+IPO+Hwh4dxUOsdRjfo1Ol2d2s= me@home").unwrap();
+    /// assert_eq!(key.curve(), Some(sshkeys::CurveKind::Nistp256));
+    ///
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert_eq!(key.curve(), None);
+    /// ```
+    pub fn curve(&self) -> Option<CurveKind> {
+        match self.kind {
+            PublicKeyKind::Ecdsa(ref k) => Some(k.curve.kind.clone()),
+            PublicKeyKind::SkEcdsa(ref k) => Some(k.curve.kind.clone()),
+            PublicKeyKind::Rsa(_)
+            | PublicKeyKind::Dsa(_)
+            | PublicKeyKind::Ed25519(_)
+            | PublicKeyKind::Ed448(_)
+            | PublicKeyKind::SkEd25519(_)
+            | PublicKeyKind::Xmss(_) => None,
+        }
+    }
+
+    /// Validates the public key, rejecting keys with parameters which are never legitimate.
+    ///
+    /// RSA and DSA keys are validated using `RsaPublicKey::validate` and `DsaPublicKey::validate`.
+    /// ECDSA keys must not be the zero point, and ED25519 keys must not be a point of small order.
+    /// ED448 keys must be 57 bytes long and not all zeros.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert!(key.validate().is_ok());
+    ///
+    /// let zero = sshkeys::PublicKey::from_ed25519(vec![0; 32]);
+    /// assert!(zero.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        match self.kind {
+            PublicKeyKind::Rsa(ref k) => k.validate(),
+            PublicKeyKind::Dsa(ref k) => k.validate(),
+            PublicKeyKind::Ecdsa(ref k) => validate_point(&k.key),
+            PublicKeyKind::SkEcdsa(ref k) => validate_point(&k.key),
+            PublicKeyKind::Ed25519(ref k) => validate_ed25519(&k.key),
+            PublicKeyKind::SkEd25519(ref k) => validate_ed25519(&k.key),
+            PublicKeyKind::Ed448(ref k) => {
+                if k.key.len() != 57 {
+                    return Err(Error::with_kind(ErrorKind::InvalidFormat));
+                }
+
+                if k.key.iter().all(|b| *b == 0) {
+                    return Err(Error::with_kind(ErrorKind::WeakKey(
+                        "ED448 key is zero".to_string(),
+                    )));
+                }
+
+                Ok(())
+            }
+            // The size of XMSS keys depends on the parameter set, which is not validated
+            PublicKeyKind::Xmss(_) => Ok(()),
+        }
+    }
+
+    /// Returns `true` if the key type is believed to be secure against attacks using
+    /// quantum computers, which is currently only the case for XMSS keys.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert!(!key.is_post_quantum());
+    /// ```
+    pub fn is_post_quantum(&self) -> bool {
+        match self.kind {
+            PublicKeyKind::Xmss(_) => true,
+            PublicKeyKind::Rsa(_)
+            | PublicKeyKind::Dsa(_)
+            | PublicKeyKind::Ecdsa(_)
+            | PublicKeyKind::Ed25519(_)
+            | PublicKeyKind::Ed448(_)
+            | PublicKeyKind::SkEd25519(_)
+            | PublicKeyKind::SkEcdsa(_) => false,
+        }
+    }
+
+    /// Returns `true` if the key should no longer be used according to current best practices.
+    ///
+    /// DSA keys are deprecated, as OpenSSH has disabled them by default since OpenSSH 7.0,
+    /// and so are RSA keys with a modulus smaller than 2048 bits.
+    /// The reasons are described by `PublicKey::security_notes`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert!(!key.is_deprecated());
+    /// ```
+    pub fn is_deprecated(&self) -> bool {
+        !self.security_notes().is_empty()
+    }
+
+    /// Returns human-readable notes describing why the key is deprecated, if it is.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_path("tests/test-keys/id_dsa_1024.pub").unwrap();
+    /// assert!(key.is_deprecated());
+    /// assert_eq!(key.security_notes(), vec!["DSA keys are deprecated and disabled by default since OpenSSH 7.0"]);
+    /// ```
+    pub fn security_notes(&self) -> Vec<String> {
+        let mut notes = Vec::new();
+
+        match self.kind {
+            PublicKeyKind::Dsa(_) => {
+                notes.push(
+                    "DSA keys are deprecated and disabled by default since OpenSSH 7.0".to_string(),
+                );
+            }
+            PublicKeyKind::Rsa(_) if self.bits() < RSA_RECOMMENDED_BITS => {
+                notes.push(format!(
+                    "RSA key of {} bits is smaller than the recommended {} bits",
+                    self.bits(),
+                    RSA_RECOMMENDED_BITS
+                ));
+            }
+            _ => {}
+        }
 
-        PublicKey::from_string(&contents)
+        notes
     }
 
-    /// Reads an OpenSSH public key from a given string.
-    ///
-    /// # Examples
+    /// Encodes the public key in an OpenSSH compatible format.
     ///
+    /// # Example
     /// ```rust
     /// # use sshkeys;
     /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
-    /// let fp = key.fingerprint();
-    /// assert_eq!(fp.hash, "ciQkdxjFUhk2E2vRkWJD9kB8pi+EneOkaCJJHNWzPC4");
+    /// assert_eq!(key.encode(), vec![0, 0, 0, 11, 115, 115, 104, 45, 101, 100, 50, 53, 53, 49, 57, 0, 0, 0, 32, 121, 27, 123, 184, 48, 199, 187, 52, 118, 80, 41, 16, 76, 233, 83, 35, 128, 62, 188, 207, 47, 46, 28, 204, 70, 112, 254, 200, 124, 155, 202, 221]);
     /// ```
-    pub fn from_string(contents: &str) -> Result<PublicKey> {
-        let mut iter = contents.split_whitespace();
-
-        let kt_name = iter
-            .next()
-            .ok_or(Error::with_kind(ErrorKind::InvalidFormat))?;
-
-        let data = iter
-            .next()
-            .ok_or(Error::with_kind(ErrorKind::InvalidFormat))?;
-
-        let comment = iter.next().map(|v| String::from(v));
-
-        let kt = KeyType::from_name(&kt_name)?;
-
-        let decoded = base64::decode(&data)?;
-        let mut reader = Reader::new(&decoded);
-
-        // Validate key type before reading rest of the data
-        let kt_from_reader = reader.read_string()?;
-        if kt_name != kt_from_reader {
-            return Err(Error::with_kind(ErrorKind::KeyTypeMismatch));
-        }
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = Writer::with_capacity(self.encoded_len());
 
-        // Construct a new `PublicKey` value and preserve the `comment` value.
-        let k = PublicKey::from_reader(&kt_name, &mut reader)?;
-        let key = PublicKey {
-            key_type: kt,
-            kind: k.kind,
-            comment: comment,
-        };
+        w.write_string(self.key_type.plain);
+        self.write_key_data(&mut w);
 
-        Ok(key)
+        w.into_bytes()
     }
 
-    /// Reads a public key from a given byte sequence.
+    /// Alias of `PublicKey::encode`.
     ///
-    /// The byte sequence is expected to be the base64 decoded body of the public key.
+    /// `PublicKey::encode` already writes the `mpint` values without redundant leading
+    /// zero bytes, regardless of how the key was constructed, so equivalent keys always
+    /// have the same encoding and fingerprint. The key blob the key was parsed from is
+    /// available as `PublicKey::original_blob`.
     ///
     /// # Example
-    ///
     /// ```rust
     /// # use sshkeys;
-    /// let data = vec![0, 0, 0, 11, 115, 115, 104, 45,
-    ///                 101, 100, 50, 53, 53, 49, 57,
-    ///                 0, 0, 0, 32, 121, 27, 123, 184,
-    ///                 48, 199, 187, 52, 118, 80, 41, 16,
-    ///                 76, 233, 83, 35, 128, 62, 188,
-    ///                 207, 47, 46, 28, 204, 70, 112,
-    ///                 254, 200, 124, 155, 202, 221];
-    ///
-    /// let key = sshkeys::PublicKey::from_bytes(&data).unwrap();
-    /// let fp = key.fingerprint();
-    /// assert_eq!(fp.hash, "ciQkdxjFUhk2E2vRkWJD9kB8pi+EneOkaCJJHNWzPC4");
+    /// let a = sshkeys::PublicKey::from_rsa(vec![0, 1, 0, 1], vec![0, 0, 0xc5, 0x1b]);
+    /// let b = sshkeys::PublicKey::new(
+    ///     sshkeys::KeyType::from_name("ssh-rsa").unwrap(),
+    ///     sshkeys::PublicKeyKind::Rsa(sshkeys::RsaPublicKey {
+    ///         e: vec![1, 0, 1],
+    ///         n: vec![0xc5, 0x1b],
+    ///     }),
+    ///     None,
+    /// );
+    /// assert_eq!(a.canonical_encode(), a.encode());
+    /// assert_eq!(a.canonical_encode(), b.canonical_encode());
     /// ```
-    pub fn from_bytes<T: ?Sized + AsRef<[u8]>>(data: &T) -> Result<PublicKey> {
-        let mut reader = Reader::new(&data);
-        let kt_name = reader.read_string()?;
-
-        PublicKey::from_reader(&kt_name, &mut reader)
+    pub fn canonical_encode(&self) -> Vec<u8> {
+        self.encode()
     }
 
-    // This function is used for extracting a public key from an existing reader, e.g.
-    // we already have a reader for reading an OpenSSH certificate key and
-    // we want to extract the public key information from it.
-    pub(crate) fn from_reader(kt_name: &str, reader: &mut Reader) -> Result<PublicKey> {
-        let kt = KeyType::from_name(&kt_name)?;
-
-        let kind = match kt.kind {
-            KeyTypeKind::Rsa | KeyTypeKind::RsaCert => {
-                let k = RsaPublicKey {
-                    e: reader.read_mpint()?,
-                    n: reader.read_mpint()?,
-                };
-
-                PublicKeyKind::Rsa(k)
-            }
-            KeyTypeKind::Dsa | KeyTypeKind::DsaCert => {
-                let k = DsaPublicKey {
-                    p: reader.read_mpint()?,
-                    q: reader.read_mpint()?,
-                    g: reader.read_mpint()?,
-                    y: reader.read_mpint()?,
-                };
-
-                PublicKeyKind::Dsa(k)
-            }
-            KeyTypeKind::Ecdsa | KeyTypeKind::EcdsaCert => {
-                let identifier = reader.read_string()?;
-                let curve = Curve::from_identifier(&identifier)?;
-                let key = reader.read_bytes()?;
-                let k = EcdsaPublicKey {
-                    curve: curve,
-                    key: key,
-                };
-
-                PublicKeyKind::Ecdsa(k)
-            }
-            KeyTypeKind::Ed25519 | KeyTypeKind::Ed25519Cert => {
-                let k = Ed25519PublicKey {
-                    key: reader.read_bytes()?,
-                };
-
-                PublicKeyKind::Ed25519(k)
-            }
-        };
+    /// Alias of `PublicKey::encode`, named after the key blob it returns.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert_eq!(key.to_blob(), key.encode());
+    /// ```
+    pub fn to_blob(&self) -> Vec<u8> {
+        self.encode()
+    }
 
-        let key = PublicKey {
-            key_type: kt,
-            kind: kind,
-            comment: None,
-        };
+    /// Returns the key blob of the public key preceded by its length as `u32` value,
+    /// the way the key blob is embedded in protocol messages, e.g. ssh-agent requests.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// let prefixed = key.to_prefixed_blob();
+    /// assert_eq!(prefixed[..4], [0, 0, 0, 51]);
+    /// assert_eq!(prefixed[4..], key.encode()[..]);
+    /// ```
+    pub fn to_prefixed_blob(&self) -> Vec<u8> {
+        let mut w = Writer::new();
+        w.write_bytes(&self.encode());
 
-        Ok(key)
+        w.into_bytes()
     }
 
-    /// Returns the number of bits of the public key.
+    /// Returns the raw public value of the key, without any type information or length prefixes.
     ///
-    /// # Example
+    /// For RSA keys this is the modulus only, as the modulus is what identifies an RSA key,
+    /// and for DSA keys this is the public value `y`. Both are returned as unsigned big-endian
+    /// numbers without leading zero bytes. For ECDSA keys this is the encoded curve point and
+    /// for ED25519 and ED448 keys the encoded public key. The keys of security keys are
+    /// returned the same way, without the application.
     ///
+    /// # Example
     /// ```rust
     /// # use sshkeys;
     /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
-    /// assert_eq!(key.bits(), 256);
+    /// let data = key.key_data();
+    /// assert_eq!(data.len(), 32);
+    /// assert_eq!(data[..], key.encode()[19..]);
     /// ```
-    pub fn bits(&self) -> usize {
-        match self.kind {
-            // For RSA public key the size of the key is the number of bits of the modulus
-            PublicKeyKind::Rsa(ref k) => k.n.len() * 8,
-            // For DSA public keys the size of the key is the number of bits of the `p` parameter
-            PublicKeyKind::Dsa(ref k) => k.p.len() * 8,
-            // ECDSA key size depends on the curve
-            PublicKeyKind::Ecdsa(ref k) => match k.curve.kind {
-                CurveKind::Nistp256 => 256,
-                CurveKind::Nistp384 => 384,
-                CurveKind::Nistp521 => 521,
-            },
-            // ED25519 key size is 256 bits
-            // https://tools.ietf.org/html/draft-josefsson-eddsa-ed25519-03#section-5.5
-            PublicKeyKind::Ed25519(_) => 256,
-        }
+    pub fn key_data(&self) -> Vec<u8> {
+        let data = match self.kind {
+            PublicKeyKind::Rsa(ref k) => strip_leading_zeros(&k.n),
+            PublicKeyKind::Dsa(ref k) => strip_leading_zeros(&k.y),
+            PublicKeyKind::Ecdsa(ref k) => &k.key,
+            PublicKeyKind::Ed25519(ref k) => &k.key,
+            PublicKeyKind::Ed448(ref k) => &k.key,
+            PublicKeyKind::SkEd25519(ref k) => &k.key,
+            PublicKeyKind::SkEcdsa(ref k) => &k.key,
+            PublicKeyKind::Xmss(ref k) => &k.key,
+        };
+
+        data.to_vec()
     }
 
-    /// Encodes the public key in an OpenSSH compatible format.
+    /// Returns the number of bytes `PublicKey::encode` produces, without encoding the key.
     ///
     /// # Example
     /// ```rust
     /// # use sshkeys;
     /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
-    /// assert_eq!(key.encode(), vec![0, 0, 0, 11, 115, 115, 104, 45, 101, 100, 50, 53, 53, 49, 57, 0, 0, 0, 32, 121, 27, 123, 184, 48, 199, 187, 52, 118, 80, 41, 16, 76, 233, 83, 35, 128, 62, 188, 207, 47, 46, 28, 204, 70, 112, 254, 200, 124, 155, 202, 221]);
+    /// assert_eq!(key.encoded_len(), 51);
+    /// assert_eq!(key.encoded_len(), key.encode().len());
     /// ```
-    pub fn encode(&self) -> Vec<u8> {
-        let mut w = Writer::new();
+    pub fn encoded_len(&self) -> usize {
+        let data_len = match self.kind {
+            PublicKeyKind::Rsa(ref k) => mpint_len(&k.e) + mpint_len(&k.n),
+            PublicKeyKind::Dsa(ref k) => {
+                mpint_len(&k.p) + mpint_len(&k.q) + mpint_len(&k.g) + mpint_len(&k.y)
+            }
+            PublicKeyKind::Ecdsa(ref k) => bytes_len(k.curve.identifier) + bytes_len(&k.key),
+            PublicKeyKind::Ed25519(ref k) => bytes_len(&k.key),
+            PublicKeyKind::Ed448(ref k) => bytes_len(&k.key),
+            PublicKeyKind::SkEd25519(ref k) => bytes_len(&k.key) + bytes_len(&k.application),
+            PublicKeyKind::SkEcdsa(ref k) => {
+                bytes_len(k.curve.identifier) + bytes_len(&k.key) + bytes_len(&k.application)
+            }
+            PublicKeyKind::Xmss(ref k) => bytes_len(&k.oid) + bytes_len(&k.key),
+        };
 
-        w.write_string(self.key_type.plain);
+        bytes_len(self.key_type.plain) + data_len
+    }
+
+    // Writes the key specific fields of the public key, which follow the key type.
+    // This is also used for writing the public key part of certificates.
+    pub(crate) fn write_key_data(&self, w: &mut Writer) {
         match self.kind {
             PublicKeyKind::Rsa(ref k) => {
                 w.write_mpint(&k.e);
@@ -431,15 +2512,44 @@ impl PublicKey {
                 w.write_mpint(&k.y);
             }
             PublicKeyKind::Ecdsa(ref k) => {
-                w.write_string(&k.curve.identifier);
+                w.write_string(k.curve.identifier);
                 w.write_bytes(&k.key);
             }
             PublicKeyKind::Ed25519(ref k) => {
                 w.write_bytes(&k.key);
             }
+            PublicKeyKind::Ed448(ref k) => {
+                w.write_bytes(&k.key);
+            }
+            PublicKeyKind::SkEd25519(ref k) => {
+                w.write_bytes(&k.key);
+                w.write_string(&k.application);
+            }
+            PublicKeyKind::SkEcdsa(ref k) => {
+                w.write_string(k.curve.identifier);
+                w.write_bytes(&k.key);
+                w.write_string(&k.application);
+            }
+            PublicKeyKind::Xmss(ref k) => {
+                w.write_string(&k.oid);
+                w.write_bytes(&k.key);
+            }
         }
+    }
 
-        w.into_bytes()
+    /// Returns `true` if both public keys have the same encoded form.
+    ///
+    /// Comments are not taken into account when comparing the keys.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let a = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// let b = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@work").unwrap();
+    /// assert!(a.matches(&b));
+    /// ```
+    pub fn matches(&self, other: &PublicKey) -> bool {
+        self.encode() == other.encode()
     }
 
     /// Computes the fingerprint of the public key using the
@@ -452,7 +2562,7 @@ impl PublicKey {
     /// # fn example() -> sshkeys::Result<()> {
     /// let key = sshkeys::PublicKey::from_path("/path/to/id_ed25519.pub")?;
     /// let fp = key.fingerprint();
-    /// println!("{}", fp.hash);
+    /// println!("{}", fp.hash());
     /// # Ok(())
     /// # }
     /// ```
@@ -470,7 +2580,7 @@ impl PublicKey {
     /// # fn example() -> sshkeys::Result<()> {
     /// let key = sshkeys::PublicKey::from_path("/path/to/id_ed25519.pub").unwrap();
     /// let sha512fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
-    /// println!("{}", sha512fp.hash);
+    /// println!("{}", sha512fp.hash());
     /// # Ok(())
     /// # }
     /// ```
@@ -478,6 +2588,69 @@ impl PublicKey {
         Fingerprint::compute(kind, &self.encode())
     }
 
+    /// Computes the SHA256, SHA384 and SHA512 fingerprints of the public key.
+    ///
+    /// The key is encoded only once for computing all three fingerprints.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// let (sha256fp, sha384fp, sha512fp) = key.fingerprints();
+    /// assert_eq!(sha256fp.hash(), "ciQkdxjFUhk2E2vRkWJD9kB8pi+EneOkaCJJHNWzPC4");
+    /// assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
+    /// assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
+    /// ```
+    pub fn fingerprints(&self) -> (Fingerprint, Fingerprint, Fingerprint) {
+        let data = self.encode();
+
+        (
+            Fingerprint::compute(FingerprintKind::Sha256, &data),
+            Fingerprint::compute(FingerprintKind::Sha384, &data),
+            Fingerprint::compute(FingerprintKind::Sha512, &data),
+        )
+    }
+
+    /// Generates the randomart of the public key using a given
+    /// fingerprint representation, the same way `ssh-keygen -lv` does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// let art = key.randomart(sshkeys::FingerprintKind::Sha256);
+    /// assert!(art.starts_with("+--[ED25519 256]--+"));
+    /// assert!(art.ends_with("+----[SHA256]-----+"));
+    /// ```
+    pub fn randomart(&self, kind: FingerprintKind) -> String {
+        let digest = compute_digest(&kind, &self.encode());
+
+        // Fall back to the key type only if the bits do not fit in the border
+        let mut title = format!("[{} {}]", self.key_type.short_name, self.bits());
+        if title.len() > RANDOMART_WIDTH + 1 {
+            title = format!("[{}]", self.key_type.short_name);
+        }
+        let footer = format!("[{}]", kind);
+
+        render_randomart(&digest, &title, &footer)
+    }
+
+    /// Returns the bubblebabble encoding of the SHA1 digest of the public key,
+    /// the same way `ssh-keygen -B` does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert_eq!(key.bubblebabble(), "xobeg-bebef-noput-citep-kycar-fukuc-kugon-vovev-bides-pogaf-rixyx");
+    /// ```
+    pub fn bubblebabble(&self) -> String {
+        encode_bubblebabble(&Sha1::digest(&self.encode()))
+    }
+
     /// Writes the public key to a given writer.
     ///
     /// # Example
@@ -491,12 +2664,204 @@ impl PublicKey {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "std")]
     pub fn write<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
-        let encoded = self.encode();
-        let data = base64::encode(&encoded);
+        writeln!(w, "{}", self.to_key_string())
+    }
+
+    /// Writes the public key to a given path in the OpenSSH one-line format,
+    /// followed by a trailing newline.
+    ///
+    /// The file is created if it does not exist and truncated if it does.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// # fn example() -> sshkeys::Result<()> {
+    /// let key = sshkeys::PublicKey::from_path("/path/to/id_ed25519.pub")?;
+    /// key.write_to_path("/path/to/id_ed25519_copy.pub")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut file = File::create(path)?;
+        self.write(&mut file)?;
+
+        Ok(())
+    }
+
+    /// Returns the public key in the RFC 4716 SSH2 format.
+    ///
+    /// The base64 encoded key is wrapped at 70 characters and the comment, if any,
+    /// is written as a quoted `Comment` header. Header lines longer than 72 bytes
+    /// are continued on the next line, as required by RFC 4716, section 3.3.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// assert_eq!(key.to_rfc4716(), "---- BEGIN SSH2 PUBLIC KEY ----
+    /// Comment: \"me@home\"
+    /// AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd
+    /// ---- END SSH2 PUBLIC KEY ----
+    /// ");
+    /// ```
+    pub fn to_rfc4716(&self) -> String {
+        self.rfc4716_string(self.comment.as_deref(), RFC4716_LINE_LEN)
+    }
+
+    /// Returns the public key in the RFC 4716 SSH2 format the way PuTTYgen exports it,
+    /// which is the format PuTTY imports public keys from.
+    ///
+    /// Unlike `PublicKey::to_rfc4716` the base64 encoded key is wrapped at 64 characters
+    /// and the `Comment` header is always present, as PuTTY expects it. If the key has
+    /// no comment, the header is written with an empty value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd").unwrap();
+    /// assert_eq!(key.to_putty_public(), "---- BEGIN SSH2 PUBLIC KEY ----
+    /// Comment: \"\"
+    /// AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8
+    /// m8rd
+    /// ---- END SSH2 PUBLIC KEY ----
+    /// ");
+    /// ```
+    pub fn to_putty_public(&self) -> String {
+        let comment = self.comment.as_deref().unwrap_or("");
+
+        self.rfc4716_string(Some(comment), PUTTY_LINE_LEN)
+    }
+
+    // Returns the public key in the RFC 4716 SSH2 format with the given comment header,
+    // with the base64 encoded key wrapped at the given length.
+    fn rfc4716_string(&self, comment: Option<&str>, line_len: usize) -> String {
+        let mut lines = vec![RFC4716_BEGIN.to_string()];
+
+        if let Some(c) = comment {
+            let header = format!("Comment: \"{}\"", c);
+            let mut rest = header.as_str();
+
+            // Continued lines end with a backslash, which counts towards the line length.
+            while rest.len() > RFC4716_HEADER_LEN {
+                let mut offset = RFC4716_HEADER_LEN - 1;
+                while !rest.is_char_boundary(offset) {
+                    offset -= 1;
+                }
+
+                let (line, next) = rest.split_at(offset);
+                lines.push(format!("{}\\", line));
+                rest = next;
+            }
+            lines.push(rest.to_string());
+        }
+
+        let data = self.encode_base64();
+        for chunk in data.as_bytes().chunks(line_len) {
+            // The base64 alphabet is ASCII, so the chunks are always valid UTF-8
+            lines.push(String::from_utf8_lossy(chunk).into_owned());
+        }
+
+        lines.push(RFC4716_END.to_string());
+        lines.push(String::new());
+
+        lines.join("\n")
+    }
+
+    /// Returns the public key in the OpenSSH one-line format, as found in `.pub` files.
+    ///
+    /// The line consists of the key type, the base64 encoded key and the comment, if any.
+    /// The key blob kept by `PublicKey::from_string_preserving` is used, if any.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let line = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home";
+    /// let key = sshkeys::PublicKey::from_string(line).unwrap();
+    /// assert_eq!(key.to_key_string(), line);
+    /// ```
+    pub fn to_key_string(&self) -> String {
+        let data = self.encode_base64();
+        match self.comment {
+            Some(ref c) => format!("{} {} {}", self.key_type.plain, data, c),
+            None => format!("{} {}", self.key_type.plain, data),
+        }
+    }
+
+    /// Returns the key blob the key was parsed from, if it was kept by
+    /// `PublicKey::from_string_preserving`.
+    ///
+    /// Keys whose type or kind was modified after parsing return `None`, as the key
+    /// blob no longer represents them.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// // The exponent of the key has a redundant leading zero byte
+    /// let line = "ssh-rsa AAAAB3NzaC1yc2EAAAAEAAEAAQAAAAMAxRs=";
+    /// assert_eq!(sshkeys::PublicKey::from_string(line).unwrap().original_blob(), None);
+    ///
+    /// let mut key = sshkeys::PublicKey::from_string_preserving(line).unwrap();
+    /// assert_eq!(key.original_blob().unwrap()[11..15], [0, 0, 0, 4]);
+    ///
+    /// key.kind = sshkeys::PublicKey::from_rsa(vec![3], vec![0xc5, 0x1b]).kind;
+    /// assert_eq!(key.original_blob(), None);
+    /// ```
+    pub fn original_blob(&self) -> Option<&[u8]> {
+        match self.original_blob {
+            Some(ref o) if o.key_type == self.key_type && o.kind == self.kind => Some(&o.blob),
+            _ => None,
+        }
+    }
+
+    // Returns the base64 encoded key blob of the key, which is the original key blob
+    // if it was kept and the key was not modified since.
+    fn encode_base64(&self) -> String {
+        match self.original_blob() {
+            Some(blob) => base64::encode(blob),
+            None => base64::encode(self.encode()),
+        }
+    }
+
+    /// Returns the public key in the OpenSSH one-line format, with the base64 encoded
+    /// key wrapped at the given width, which is required by some third-party parsers.
+    ///
+    /// The key type precedes the first line of the base64 encoded key and the comment,
+    /// if any, follows the last line. A width of zero disables wrapping, which is the
+    /// same as `PublicKey::to_key_string`.
+    ///
+    /// The wrapped format is meant for display only. Neither OpenSSH nor
+    /// `PublicKey::from_string` accept keys spanning several lines in this format,
+    /// use `PublicKey::to_rfc4716` for a multi-line format which can be read back.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// let wrapped = key.to_key_string_wrapped(64);
+    /// assert_eq!(wrapped, "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8
+    /// m8rd me@home");
+    /// assert!(sshkeys::PublicKey::from_string(&wrapped).is_err());
+    /// ```
+    pub fn to_key_string_wrapped(&self, width: usize) -> String {
+        if width == 0 {
+            return self.to_key_string();
+        }
+
+        let data = self.encode_base64();
+        let lines: Vec<String> = data
+            .as_bytes()
+            .chunks(width)
+            // The base64 alphabet is ASCII, so the chunks are always valid UTF-8
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+            .collect();
+        let data = lines.join("\n");
+
         match self.comment {
-            Some(ref c) => w.write_fmt(format_args!("{} {} {}\n", self.key_type.name, data, c)),
-            None => w.write_fmt(format_args!("{} {}\n", self.key_type.name, data)),
+            Some(ref c) => format!("{} {} {}", self.key_type.plain, data, c),
+            None => format!("{} {}", self.key_type.plain, data),
         }
     }
 }
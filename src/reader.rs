@@ -1,3 +1,7 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str;
+
 use super::error::{Error, ErrorKind, Result};
 
 use byteorder::{BigEndian, ByteOrder};
@@ -21,7 +25,7 @@ impl<'a> Reader<'a> {
     /// let num = reader.read_u32().unwrap();
     /// assert_eq!(num, 42);
     /// ```
-    pub fn new<T: ?Sized + AsRef<[u8]>>(inner: &T) -> Reader {
+    pub fn new<T: ?Sized + AsRef<[u8]>>(inner: &T) -> Reader<'_> {
         Reader {
             inner: inner.as_ref(),
             offset: 0,
@@ -30,6 +34,8 @@ impl<'a> Reader<'a> {
 
     /// Sets the `Reader` current offset to a given position.
     ///
+    /// This is the same as `set_position`.
+    ///
     /// # Example
     /// ```rust
     /// # use sshkeys;
@@ -42,16 +48,128 @@ impl<'a> Reader<'a> {
     /// assert_eq!(num_42_again, 42);
     /// ```
     pub fn set_offset(&mut self, offset: usize) -> Result<()> {
-        self.offset = offset;
+        self.set_position(offset)
+    }
+
+    /// Sets the `Reader` current offset to a given position.
+    ///
+    /// The position may be at most the length of the wrapped byte sequence,
+    /// otherwise an error is returned and the current offset is left unchanged.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 42];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// reader.read_u32().unwrap();
+    /// reader.set_position(2).unwrap();
+    /// assert_eq!(reader.remaining(), 2);
+    /// assert!(reader.set_position(5).is_err());
+    /// assert_eq!(reader.position(), 2);
+    /// ```
+    pub fn set_position(&mut self, pos: usize) -> Result<()> {
+        if pos > self.inner.len() {
+            return Err(Error::with_kind(ErrorKind::UnexpectedEof {
+                offset: self.inner.len(),
+                needed: pos - self.inner.len(),
+            }));
+        }
+
+        self.offset = pos;
 
         Ok(())
     }
 
+    /// Calls the given closure with the `Reader` and rewinds the `Reader` to its
+    /// current position if the closure returns an error.
+    ///
+    /// This is useful for speculatively reading a value, which can be read
+    /// differently if the first attempt fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 2, 0xff, 0xfe];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    ///
+    /// // The value is not valid UTF-8, so read it as a byte sequence instead
+    /// let value = match reader.with_checkpoint(|r| r.read_string()) {
+    ///     Ok(s) => s.into_bytes(),
+    ///     Err(_) => reader.read_bytes().unwrap(),
+    /// };
+    /// assert_eq!(value, [0xff, 0xfe]);
+    /// ```
+    pub fn with_checkpoint<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Reader<'a>) -> Result<T>,
+    {
+        let checkpoint = self.offset;
+        let result = f(self);
+        if result.is_err() {
+            self.offset = checkpoint;
+        }
+
+        result
+    }
+
+    /// Returns the current offset of the `Reader`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 42];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// assert_eq!(reader.position(), 0);
+    /// reader.read_u32().unwrap();
+    /// assert_eq!(reader.position(), 4);
+    /// ```
+    pub fn position(&self) -> usize {
+        self.offset
+    }
+
+    // Returns the part of the wrapped byte sequence which has been read so far.
+    pub(crate) fn consumed(&self) -> &'a [u8] {
+        &self.inner[..self.offset]
+    }
+
+    /// Returns the number of bytes left to be read from the wrapped byte sequence.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 42, 0, 0];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// assert_eq!(reader.remaining(), 6);
+    /// reader.read_u32().unwrap();
+    /// assert_eq!(reader.remaining(), 2);
+    /// ```
+    pub fn remaining(&self) -> usize {
+        self.inner.len().saturating_sub(self.offset)
+    }
+
+    /// Returns the next `n` bytes of the wrapped byte sequence without advancing the `Reader`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 42];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// assert_eq!(reader.peek_bytes(2).unwrap(), [0, 0]);
+    /// assert_eq!(reader.position(), 0);
+    /// assert!(reader.peek_bytes(5).is_err());
+    /// ```
+    pub fn peek_bytes(&self, n: usize) -> Result<&'a [u8]> {
+        self.slice_at(self.offset, n)
+    }
+
     /// Reads a byte buffer from the wrapped byte sequence and
     /// returns it as a `Vec<u8>`.
     /// The buffer is represented by it's length as `u32` value
     /// followed by the actual bytes to read.
     ///
+    /// The length is checked against the remaining bytes before anything is
+    /// allocated, so a length exceeding the wrapped byte sequence results in an error.
+    ///
     /// # Example
     /// ```rust
     /// # use sshkeys;
@@ -61,31 +179,36 @@ impl<'a> Reader<'a> {
     /// assert_eq!(bytes, [97, 32, 116, 101, 115, 116, 32, 115, 116, 114, 105, 110, 103]);
     /// ```
     pub fn read_bytes(&mut self) -> Result<Vec<u8>> {
-        if self.offset >= self.inner.len() {
-            return Err(Error::with_kind(ErrorKind::UnexpectedEof));
-        }
-
-        let slice = &self.inner[self.offset..];
-
-        if slice.len() < 4 {
-            return Err(Error::with_kind(ErrorKind::InvalidFormat));
-        }
-
-        let size = BigEndian::read_u32(&slice[..4]) as usize;
+        // The buffer is only copied once it is known to be within bounds
+        let result = self.read_bytes_ref()?.to_vec();
 
-        if slice.len() < size + 4 {
-            return Err(Error::with_kind(ErrorKind::InvalidFormat));
-        }
+        Ok(result)
+    }
 
+    /// Reads a byte buffer from the wrapped byte sequence the same way as `read_bytes`,
+    /// but returns a slice of the wrapped byte sequence instead of copying it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 3, 1, 2, 3, 0, 0, 0, 1];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// let bytes = reader.read_bytes_ref().unwrap();
+    /// assert_eq!(bytes, [1, 2, 3]);
+    /// assert!(reader.read_bytes_ref().is_err());
+    /// ```
+    pub fn read_bytes_ref(&mut self) -> Result<&'a [u8]> {
+        let size = BigEndian::read_u32(self.slice_at(self.offset, 4)?) as usize;
+        let result = self.slice_at(self.offset.saturating_add(4), size)?;
         self.offset += size + 4;
-        let result = slice[4..size + 4].to_vec();
 
         Ok(result)
     }
 
     /// Reads an `mpint` value from the wrapped byte sequence.
     ///
-    /// Drops the leading byte if it's value is zero according to the RFC 4251, section 5.
+    /// Drops the leading zero bytes according to the RFC 4251, section 5.
+    /// The value zero is represented by an empty byte sequence.
     ///
     /// # Example
     /// ```rust
@@ -96,13 +219,28 @@ impl<'a> Reader<'a> {
     /// assert_eq!(mpint, [1, 0, 1]);
     /// ```
     pub fn read_mpint(&mut self) -> Result<Vec<u8>> {
-        let bytes = self.read_bytes()?;
+        let result = self.read_mpint_ref()?.to_vec();
 
-        if bytes[0] == 0 {
-            return Ok(bytes[1..].to_vec());
-        }
+        Ok(result)
+    }
 
-        Ok(bytes)
+    /// Reads an `mpint` value from the wrapped byte sequence the same way as `read_mpint`,
+    /// but returns a slice of the wrapped byte sequence instead of copying it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 4, 0, 0x80, 0, 1];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// let mpint = reader.read_mpint_ref().unwrap();
+    /// assert_eq!(mpint, [0x80, 0, 1]);
+    /// ```
+    pub fn read_mpint_ref(&mut self) -> Result<&'a [u8]> {
+        let bytes = self.read_bytes_ref()?;
+
+        let offset = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+
+        Ok(&bytes[offset..])
     }
 
     /// Reads a `string` value from the wrapped byte sequence and
@@ -123,6 +261,63 @@ impl<'a> Reader<'a> {
         Ok(result)
     }
 
+    /// Reads a `string` value from the wrapped byte sequence the same way as `read_string`,
+    /// but returns a `&str` borrowed from the wrapped byte sequence instead of copying it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 0, 0, 11, 115, 115, 104, 45, 101, 100, 50, 53, 53, 49, 57];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// let result = reader.read_str().unwrap();
+    /// assert_eq!(result, "ssh-ed25519");
+    /// ```
+    pub fn read_str(&mut self) -> Result<&'a str> {
+        let bytes = self.read_bytes_ref()?;
+
+        match str::from_utf8(bytes) {
+            Ok(v) => Ok(v),
+            // The bytes are only copied to report the same error as `read_string`
+            Err(_) => Err(String::from_utf8(bytes.to_vec()).unwrap_err().into()),
+        }
+    }
+
+    /// Reads a single byte from the wrapped byte sequence and returns it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![42];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// let num = reader.read_u8().unwrap();
+    /// assert_eq!(num, 42);
+    /// assert!(reader.read_u8().is_err());
+    /// ```
+    pub fn read_u8(&mut self) -> Result<u8> {
+        let value = self.slice_at(self.offset, 1)?[0];
+        self.offset += 1;
+
+        Ok(value)
+    }
+
+    /// Reads a `boolean` value from the wrapped byte sequence and returns it.
+    /// Any non-zero value is interpreted as `true`, as described in RFC 4251, section 5.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let data = vec![0, 1, 42];
+    /// let mut reader = sshkeys::Reader::new(&data);
+    /// assert_eq!(reader.read_bool().unwrap(), false);
+    /// assert_eq!(reader.read_bool().unwrap(), true);
+    /// assert_eq!(reader.read_bool().unwrap(), true);
+    /// ```
+    pub fn read_bool(&mut self) -> Result<bool> {
+        let value = self.read_u8()?;
+
+        Ok(value != 0)
+    }
+
     /// Reads an `u32` value from the wrapped byte sequence and returns it.
     ///
     /// # Example
@@ -134,17 +329,8 @@ impl<'a> Reader<'a> {
     /// assert_eq!(num, 42);
     /// ```
     pub fn read_u32(&mut self) -> Result<u32> {
-        if self.offset >= self.inner.len() {
-            return Err(Error::with_kind(ErrorKind::UnexpectedEof));
-        }
-
-        let slice = &self.inner[self.offset..];
-        if slice.len() < 4 {
-            return Err(Error::with_kind(ErrorKind::InvalidFormat));
-        }
-
+        let value = BigEndian::read_u32(self.slice_at(self.offset, 4)?);
         self.offset += 4;
-        let value = BigEndian::read_u32(&slice[..4]);
 
         Ok(value)
     }
@@ -160,18 +346,24 @@ impl<'a> Reader<'a> {
     /// assert_eq!(num, 42);
     /// ```
     pub fn read_u64(&mut self) -> Result<u64> {
-        if self.offset >= self.inner.len() {
-            return Err(Error::with_kind(ErrorKind::UnexpectedEof));
-        }
-
-        let slice = &self.inner[self.offset..];
-        if slice.len() < 8 {
-            return Err(Error::with_kind(ErrorKind::InvalidFormat));
-        }
-
+        let value = BigEndian::read_u64(self.slice_at(self.offset, 8)?);
         self.offset += 8;
-        let value = BigEndian::read_u64(&slice[..8]);
 
         Ok(value)
     }
+
+    // Returns `size` bytes of the wrapped byte sequence starting at the given offset.
+    // If not enough bytes are available an error carrying the offset and
+    // the number of requested bytes is returned.
+    fn slice_at(&self, offset: usize, size: usize) -> Result<&'a [u8]> {
+        self.inner
+            .get(offset..)
+            .and_then(|v| v.get(..size))
+            .ok_or_else(|| {
+                Error::with_kind(ErrorKind::UnexpectedEof {
+                    offset,
+                    needed: size,
+                })
+            })
+    }
 }
@@ -2,7 +2,8 @@ extern crate serde;
 
 use self::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use super::pubkey::PublicKey;
-use std::fmt;
+use alloc::string::ToString;
+use core::fmt;
 
 impl Serialize for PublicKey {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -0,0 +1,122 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::error::{Error, ErrorKind, Result};
+use super::keytype::{KeyType, KeyTypeKind};
+use super::reader::Reader;
+
+/// A type which represents the different kinds a signature can be.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureKind {
+    /// Represents an RSA signature.
+    Rsa(Vec<u8>),
+
+    /// Represents a DSA signature.
+    Dsa(Vec<u8>),
+
+    /// Represents an ECDSA signature.
+    Ecdsa(EcdsaSignature),
+
+    /// Represents an ED25519 signature.
+    Ed25519(Vec<u8>),
+
+    /// Represents an ED448 signature.
+    Ed448(Vec<u8>),
+}
+
+/// ECDSA signature.
+/// The format of ECDSA signatures is described in RFC 5656, section 3.1.2.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EcdsaSignature {
+    /// The `r` value of the signature.
+    pub r: Vec<u8>,
+
+    /// The `s` value of the signature.
+    pub s: Vec<u8>,
+}
+
+/// A type which represents an SSH signature.
+/// The signature consists of the signature algorithm name followed by
+/// the signature blob, the format of which depends on the algorithm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    /// Signature algorithm, e.g. `rsa-sha2-512` or `ssh-ed25519`.
+    pub algorithm: String,
+
+    /// The kind of signature.
+    pub kind: SignatureKind,
+}
+
+impl Signature {
+    /// Reads a signature from a given byte sequence in the SSH wire format.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sshkeys;
+    /// let mut writer = sshkeys::Writer::new();
+    /// writer.write_string("ssh-ed25519");
+    /// writer.write_bytes(&[0; 64]);
+    /// let data = writer.into_bytes();
+    ///
+    /// let signature = sshkeys::Signature::from_bytes(&data).unwrap();
+    /// assert_eq!(signature.algorithm, "ssh-ed25519");
+    /// assert_eq!(signature.kind, sshkeys::SignatureKind::Ed25519(vec![0; 64]));
+    /// ```
+    pub fn from_bytes<T: ?Sized + AsRef<[u8]>>(data: &T) -> Result<Signature> {
+        let mut reader = Reader::new(&data);
+        let algorithm = reader.read_string()?;
+        let blob = reader.read_bytes()?;
+
+        if reader.remaining() > 0 {
+            return Err(Error::with_kind(ErrorKind::TrailingData));
+        }
+
+        let kt = signature_key_type(&algorithm)?;
+
+        let kind = match kt.kind {
+            KeyTypeKind::Rsa => SignatureKind::Rsa(blob),
+            KeyTypeKind::Dsa => SignatureKind::Dsa(blob),
+            KeyTypeKind::Ecdsa => {
+                let mut reader = Reader::new(&blob);
+                let sig = EcdsaSignature {
+                    r: reader.read_mpint()?,
+                    s: reader.read_mpint()?,
+                };
+
+                if reader.remaining() > 0 {
+                    return Err(Error::with_kind(ErrorKind::TrailingData));
+                }
+
+                SignatureKind::Ecdsa(sig)
+            }
+            KeyTypeKind::Ed25519 => SignatureKind::Ed25519(blob),
+            KeyTypeKind::Ed448 => SignatureKind::Ed448(blob),
+            _ => return Err(unknown_signature_type(&algorithm)),
+        };
+
+        let signature = Signature { algorithm, kind };
+
+        Ok(signature)
+    }
+}
+
+// Returns the type of the keys which create signatures with the given algorithm.
+// The RSA signature algorithms with SHA-2 as described in RFC 8332 use plain
+// `ssh-rsa` keys, but are no key types of their own.
+fn signature_key_type(algorithm: &str) -> Result<KeyType> {
+    let name = match algorithm {
+        "rsa-sha2-256" | "rsa-sha2-512" => "ssh-rsa",
+        _ => algorithm,
+    };
+
+    match KeyType::from_exact_name(name) {
+        Ok(kt) => Ok(kt),
+        Err(_) => Err(unknown_signature_type(algorithm)),
+    }
+}
+
+// Returns the error for signature algorithms which are not supported.
+fn unknown_signature_type(algorithm: &str) -> Error {
+    Error::with_kind(ErrorKind::UnknownSignatureType(algorithm.to_string()))
+}
@@ -0,0 +1,315 @@
+use super::error::{Error, ErrorKind, Result};
+use super::pubkey::{coordinate_len, Curve, CurveKind, PublicKey, PublicKeyKind};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+// DER tags of the ASN.1 types used by SubjectPublicKeyInfo structures.
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_NULL: u8 = 0x05;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+
+// DER encoded contents of the object identifiers of the supported algorithms and curves.
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+const OID_DSA: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x38, 0x04, 0x01];
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const OID_ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+const OID_ED448: &[u8] = &[0x2b, 0x65, 0x71];
+const OID_NISTP256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+const OID_NISTP384: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x22];
+const OID_NISTP521: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x23];
+const OID_SECP256K1: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+// A minimal reader of DER encoded values, supporting only the types
+// found in SubjectPublicKeyInfo structures.
+struct DerReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> DerReader<'a> {
+    fn new(data: &'a [u8]) -> DerReader<'a> {
+        DerReader { data }
+    }
+
+    // Returns the tag of the next value, if any.
+    fn peek_tag(&self) -> Option<u8> {
+        self.data.first().cloned()
+    }
+
+    // Reads the next value, which must have the given tag, and returns its contents.
+    fn read(&mut self, tag: u8) -> Result<&'a [u8]> {
+        let (&actual, rest) = match self.data.split_first() {
+            Some(v) => v,
+            None => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
+
+        if actual != tag {
+            return Err(Error::with_kind(ErrorKind::InvalidFormat));
+        }
+
+        // Lengths are encoded in short form, or in long form using up to four bytes.
+        // The indefinite form is not allowed by DER.
+        let (len, rest) = match rest.split_first() {
+            Some((&b, rest)) if b < 0x80 => (b as usize, rest),
+            Some((&b, rest)) if b > 0x80 && b <= 0x84 && rest.len() >= (b & 0x7f) as usize => {
+                let (bytes, rest) = rest.split_at((b & 0x7f) as usize);
+                let len = bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+                (len, rest)
+            }
+            _ => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        };
+
+        if rest.len() < len {
+            return Err(Error::with_kind(ErrorKind::InvalidFormat));
+        }
+
+        let (contents, rest) = rest.split_at(len);
+        self.data = rest;
+
+        Ok(contents)
+    }
+
+    // Reads a non-negative `INTEGER` value and returns it as a big-endian number.
+    fn read_uint(&mut self) -> Result<Vec<u8>> {
+        let contents = self.read(TAG_INTEGER)?;
+        match contents.first() {
+            Some(b) if b & 0x80 == 0 => Ok(contents.to_vec()),
+            _ => Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        }
+    }
+
+    // Reads a `BIT STRING` value, which must not have any unused bits.
+    fn read_bit_string(&mut self) -> Result<&'a [u8]> {
+        match self.read(TAG_BIT_STRING)?.split_first() {
+            Some((0, bits)) => Ok(bits),
+            _ => Err(Error::with_kind(ErrorKind::InvalidFormat)),
+        }
+    }
+
+    // Verifies that all values have been read.
+    fn finish(&self) -> Result<()> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::with_kind(ErrorKind::InvalidFormat))
+        }
+    }
+}
+
+// Encodes a DER value with the given tag and contents.
+fn encode_der(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut result = vec![tag];
+
+    // Lengths below 128 bytes are encoded in short form, others in long form
+    let len = contents.len();
+    if len < 0x80 {
+        result.push(len as u8);
+    } else {
+        let bytes = (len as u32).to_be_bytes();
+        let offset = bytes.iter().position(|b| *b != 0).unwrap_or(3);
+        result.push(0x80 | (bytes.len() - offset) as u8);
+        result.extend_from_slice(&bytes[offset..]);
+    }
+
+    result.extend_from_slice(contents);
+    result
+}
+
+// Encodes a big-endian number as a DER `INTEGER` value, which uses
+// the minimal two's complement representation of the number.
+fn encode_uint(val: &[u8]) -> Vec<u8> {
+    let offset = val.iter().position(|b| *b != 0).unwrap_or(val.len());
+    let mut contents = val[offset..].to_vec();
+    if contents.is_empty() || contents[0] & 0x80 != 0 {
+        contents.insert(0, 0);
+    }
+
+    encode_der(TAG_INTEGER, &contents)
+}
+
+// Encodes a byte sequence as a DER `BIT STRING` value without unused bits.
+fn encode_bit_string(val: &[u8]) -> Vec<u8> {
+    let mut contents = vec![0];
+    contents.extend_from_slice(val);
+
+    encode_der(TAG_BIT_STRING, &contents)
+}
+
+impl PublicKey {
+    /// Reads a public key from a DER encoded SubjectPublicKeyInfo structure,
+    /// as described in RFC 5280, section 4.1.2.7.
+    ///
+    /// RSA (RFC 3279), DSA (RFC 3279), ECDSA on the NIST P-256, P-384, P-521 and
+    /// secp256k1 curves (RFC 5480), ED25519 and ED448 (RFC 8410) public keys are supported.
+    /// ECDSA public keys must be uncompressed points.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let der = [
+    ///     0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00, 0x79, 0x1b,
+    ///     0x7b, 0xb8, 0x30, 0xc7, 0xbb, 0x34, 0x76, 0x50, 0x29, 0x10, 0x4c, 0xe9, 0x53, 0x23,
+    ///     0x80, 0x3e, 0xbc, 0xcf, 0x2f, 0x2e, 0x1c, 0xcc, 0x46, 0x70, 0xfe, 0xc8, 0x7c, 0x9b,
+    ///     0xca, 0xdd,
+    /// ];
+    /// let key = sshkeys::PublicKey::from_spki_der(&der).unwrap();
+    /// assert_eq!(key.key_type.name, "ssh-ed25519");
+    /// assert_eq!(key.fingerprint().hash(), "ciQkdxjFUhk2E2vRkWJD9kB8pi+EneOkaCJJHNWzPC4");
+    /// ```
+    pub fn from_spki_der(der: &[u8]) -> Result<PublicKey> {
+        let mut outer = DerReader::new(der);
+        let mut spki = DerReader::new(outer.read(TAG_SEQUENCE)?);
+        if !outer.data.is_empty() {
+            return Err(Error::with_kind(ErrorKind::TrailingData));
+        }
+
+        let mut algorithm = DerReader::new(spki.read(TAG_SEQUENCE)?);
+        let key_data = spki.read_bit_string()?;
+        spki.finish()?;
+
+        let oid = algorithm.read(TAG_OID)?;
+        let key = match oid {
+            OID_RSA_ENCRYPTION => {
+                // The parameters must be NULL, but are omitted by some encoders
+                if algorithm.peek_tag() == Some(TAG_NULL) {
+                    algorithm.read(TAG_NULL)?;
+                }
+
+                let mut outer = DerReader::new(key_data);
+                let mut reader = DerReader::new(outer.read(TAG_SEQUENCE)?);
+                outer.finish()?;
+                let n = reader.read_uint()?;
+                let e = reader.read_uint()?;
+                reader.finish()?;
+
+                PublicKey::from_rsa(e, n)
+            }
+            OID_DSA => {
+                let mut params = DerReader::new(algorithm.read(TAG_SEQUENCE)?);
+                let p = params.read_uint()?;
+                let q = params.read_uint()?;
+                let g = params.read_uint()?;
+                params.finish()?;
+
+                let mut reader = DerReader::new(key_data);
+                let y = reader.read_uint()?;
+                reader.finish()?;
+
+                PublicKey::from_dsa(p, q, g, y)
+            }
+            OID_EC_PUBLIC_KEY => {
+                let curve = match algorithm.read(TAG_OID)? {
+                    OID_NISTP256 => Curve::new(CurveKind::Nistp256),
+                    OID_NISTP384 => Curve::new(CurveKind::Nistp384),
+                    OID_NISTP521 => Curve::new(CurveKind::Nistp521),
+                    OID_SECP256K1 => Curve::new(CurveKind::Secp256k1),
+                    oid => return Err(Error::with_kind(ErrorKind::UnknownCurve(format_oid(oid)))),
+                };
+
+                // The same requirements apply as for the points of OpenSSH keys
+                if key_data.len() != 1 + 2 * coordinate_len(&curve) || key_data[0] != 0x04 {
+                    return Err(Error::with_kind(ErrorKind::InvalidPoint));
+                }
+
+                PublicKey::from_ecdsa(curve, key_data.to_vec())
+            }
+            OID_ED25519 if key_data.len() == 32 => PublicKey::from_ed25519(key_data.to_vec()),
+            OID_ED448 if key_data.len() == 57 => PublicKey::from_ed448(key_data.to_vec()),
+            OID_ED25519 | OID_ED448 => return Err(Error::with_kind(ErrorKind::InvalidFormat)),
+            _ => return Err(Error::with_kind(ErrorKind::UnknownKeyType(format_oid(oid)))),
+        };
+        algorithm.finish()?;
+
+        Ok(key)
+    }
+
+    /// Encodes the public key as a DER encoded SubjectPublicKeyInfo structure,
+    /// as described in RFC 5280, section 4.1.2.7.
+    ///
+    /// The same key types are supported as by `PublicKey::from_spki_der`. Security keys
+    /// cannot be represented as a SubjectPublicKeyInfo structure, as their application
+    /// would be lost, and result in an error, the same as XMSS keys.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let key = sshkeys::PublicKey::from_string("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home").unwrap();
+    /// let der = key.to_spki_der().unwrap();
+    /// assert_eq!(der[..12], [0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00]);
+    /// assert_eq!(sshkeys::PublicKey::from_spki_der(&der).unwrap(), key);
+    /// ```
+    pub fn to_spki_der(&self) -> Result<Vec<u8>> {
+        let (algorithm, key_data) = match self.kind {
+            PublicKeyKind::Rsa(ref k) => {
+                let mut algorithm = encode_der(TAG_OID, OID_RSA_ENCRYPTION);
+                algorithm.extend(encode_der(TAG_NULL, &[]));
+
+                let mut key = encode_uint(&k.n);
+                key.extend(encode_uint(&k.e));
+
+                (algorithm, encode_der(TAG_SEQUENCE, &key))
+            }
+            PublicKeyKind::Dsa(ref k) => {
+                let mut params = encode_uint(&k.p);
+                params.extend(encode_uint(&k.q));
+                params.extend(encode_uint(&k.g));
+
+                let mut algorithm = encode_der(TAG_OID, OID_DSA);
+                algorithm.extend(encode_der(TAG_SEQUENCE, &params));
+
+                (algorithm, encode_uint(&k.y))
+            }
+            PublicKeyKind::Ecdsa(ref k) => {
+                let curve = match k.curve.kind {
+                    CurveKind::Nistp256 => OID_NISTP256,
+                    CurveKind::Nistp384 => OID_NISTP384,
+                    CurveKind::Nistp521 => OID_NISTP521,
+                    CurveKind::Secp256k1 => OID_SECP256K1,
+                };
+
+                let mut algorithm = encode_der(TAG_OID, OID_EC_PUBLIC_KEY);
+                algorithm.extend(encode_der(TAG_OID, curve));
+
+                (algorithm, k.key.clone())
+            }
+            PublicKeyKind::Ed25519(ref k) => (encode_der(TAG_OID, OID_ED25519), k.key.clone()),
+            PublicKeyKind::Ed448(ref k) => (encode_der(TAG_OID, OID_ED448), k.key.clone()),
+            PublicKeyKind::SkEd25519(_) | PublicKeyKind::SkEcdsa(_) | PublicKeyKind::Xmss(_) => {
+                return Err(Error::with_kind(ErrorKind::UnsupportedKeyType(
+                    self.key_type.name.to_string(),
+                )))
+            }
+        };
+
+        let mut spki = encode_der(TAG_SEQUENCE, &algorithm);
+        spki.extend(encode_bit_string(&key_data));
+
+        Ok(encode_der(TAG_SEQUENCE, &spki))
+    }
+}
+
+// Formats the DER encoded contents of an object identifier in dotted decimal notation.
+fn format_oid(oid: &[u8]) -> String {
+    let mut result = String::new();
+    let mut value: u64 = 0;
+
+    for b in oid {
+        value = (value << 7) | u64::from(b & 0x7f);
+        if b & 0x80 != 0 {
+            continue;
+        }
+
+        // The first value encodes the first two arcs of the identifier
+        if result.is_empty() {
+            let first = core::cmp::min(value / 40, 2);
+            let _ = write!(result, "{}.{}", first, value - first * 40);
+        } else {
+            let _ = write!(result, ".{}", value);
+        }
+        value = 0;
+    }
+
+    result
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use byteorder::{BigEndian, ByteOrder};
 
 /// A `Writer` is used for encoding a key in OpenSSH compatible format.
@@ -6,6 +8,12 @@ pub struct Writer {
     inner: Vec<u8>,
 }
 
+impl Default for Writer {
+    fn default() -> Self {
+        Writer::new()
+    }
+}
+
 impl Writer {
     /// Creates a new `Writer` instance.
     ///
@@ -17,6 +25,23 @@ impl Writer {
         Writer { inner: Vec::new() }
     }
 
+    /// Creates a new `Writer` instance, which can hold at least `capacity` bytes
+    /// without reallocating its underlying byte sequence.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let mut writer = sshkeys::Writer::with_capacity(8);
+    /// writer.write_u64(42);
+    /// let bytes = writer.into_bytes();
+    /// assert!(bytes.capacity() >= 8);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Writer {
+        Writer {
+            inner: Vec::with_capacity(capacity),
+        }
+    }
+
     /// Writes a byte sequence to the underlying vector.
     /// The value is represented as a the byte sequence length,
     /// followed by the actual byte sequence.
@@ -30,11 +55,8 @@ impl Writer {
     /// assert_eq!(bytes, vec![0, 0, 0, 4, 0, 0, 0, 42]);
     /// ```
     pub fn write_bytes(&mut self, val: &[u8]) {
-        let size = val.len() as u32;
-        let mut buf = vec![0; 4];
-        BigEndian::write_u32(&mut buf, size);
-        self.inner.append(&mut buf);
-        self.inner.extend_from_slice(&val);
+        self.write_u32(val.len() as u32);
+        self.inner.extend_from_slice(val);
     }
 
     /// Writes a `string` value to the underlying byte sequence.
@@ -51,31 +73,119 @@ impl Writer {
         self.write_bytes(val.as_bytes());
     }
 
+    /// Writes a single byte to the underlying byte sequence.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let mut writer = sshkeys::Writer::new();
+    /// writer.write_u8(42);
+    /// let bytes = writer.into_bytes();
+    /// assert_eq!(bytes, [42]);
+    /// ```
+    pub fn write_u8(&mut self, val: u8) {
+        self.inner.push(val);
+    }
+
+    /// Writes a `boolean` value to the underlying byte sequence.
+    /// The value is represented as a single byte, which is either 0 or 1.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let mut writer = sshkeys::Writer::new();
+    /// writer.write_bool(true);
+    /// writer.write_bool(false);
+    /// let bytes = writer.into_bytes();
+    /// assert_eq!(bytes, [1, 0]);
+    /// ```
+    pub fn write_bool(&mut self, val: bool) {
+        self.write_u8(val as u8);
+    }
+
+    /// Writes a `u32` value to the underlying byte sequence.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let mut writer = sshkeys::Writer::new();
+    /// writer.write_u32(42);
+    /// let bytes = writer.into_bytes();
+    /// assert_eq!(bytes, [0, 0, 0, 42]);
+    /// ```
+    pub fn write_u32(&mut self, val: u32) {
+        let mut buf = [0; 4];
+        BigEndian::write_u32(&mut buf, val);
+        self.inner.extend_from_slice(&buf);
+    }
+
+    /// Writes a `u64` value to the underlying byte sequence.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let mut writer = sshkeys::Writer::new();
+    /// writer.write_u64(42);
+    /// let bytes = writer.into_bytes();
+    /// assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 42]);
+    /// ```
+    pub fn write_u64(&mut self, val: u64) {
+        let mut buf = [0; 8];
+        BigEndian::write_u64(&mut buf, val);
+        self.inner.extend_from_slice(&buf);
+    }
+
     /// Writes an `mpint` value to the underlying byte sequence.
     /// If the MSB bit of the first byte is set then the number is
     /// negative, otherwise it is positive.
     /// Positive numbers must be preceeded by a leading zero byte according to RFC 4251, section 5.
+    /// Redundant leading zero bytes of the value are not written.
     ///
     /// # Example
     /// ```rust
     /// # use sshkeys;
     /// let mut writer = sshkeys::Writer::new();
     /// writer.write_mpint(&[1, 0, 1]);
+    /// writer.write_mpint(&[0, 0, 0x80, 1]);
     /// let bytes = writer.into_bytes();
-    /// assert_eq!(bytes, [0, 0, 0, 3, 1, 0, 1]);
+    /// assert_eq!(bytes, [0, 0, 0, 3, 1, 0, 1, 0, 0, 0, 3, 0, 0x80, 1]);
     /// ```
     pub fn write_mpint(&mut self, val: &[u8]) {
-        let mut bytes = val.to_vec();
+        let offset = val.iter().position(|b| *b != 0).unwrap_or(val.len());
+        let mut bytes = val[offset..].to_vec();
 
         // If most significant bit is set then prepend a zero byte to
         // avoid interpretation as a negative number.
-        if val.get(0).unwrap_or(&0) & 0x80 != 0 {
+        if bytes.first().unwrap_or(&0) & 0x80 != 0 {
             bytes.insert(0, 0);
         }
 
         self.write_bytes(&bytes);
     }
 
+    /// Writes a nested byte sequence to the underlying byte sequence.
+    /// The closure is called with a new `Writer` instance and the resulting
+    /// bytes are written length-prefixed, the same way as `write_bytes` does.
+    /// This is useful for encoding sub-messages such as certificate critical
+    /// options and extensions.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use sshkeys;
+    /// let mut writer = sshkeys::Writer::new();
+    /// writer.write_nested(|w| {
+    ///     w.write_string("a");
+    ///     w.write_string("");
+    /// });
+    /// let bytes = writer.into_bytes();
+    /// assert_eq!(bytes, [0, 0, 0, 9, 0, 0, 0, 1, 97, 0, 0, 0, 0]);
+    /// ```
+    pub fn write_nested<F: FnOnce(&mut Writer)>(&mut self, f: F) {
+        let mut nested = Writer::new();
+        f(&mut nested);
+        self.write_bytes(&nested.into_bytes());
+    }
+
     /// Converts the `Writer` into a byte sequence.
     /// This consumes the underlying byte sequence used by the `Writer`.
     ///
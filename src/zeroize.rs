@@ -0,0 +1,147 @@
+extern crate zeroize;
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+
+use self::zeroize::Zeroize;
+use super::cert::Certificate;
+use super::pubkey::{
+    DsaPublicKey, EcdsaPublicKey, EcdsaSkPublicKey, Ed25519PublicKey, Ed25519SkPublicKey,
+    Ed448PublicKey, PublicKey, PublicKeyKind, RsaPublicKey, XmssPublicKey,
+};
+use super::signature::{EcdsaSignature, Signature, SignatureKind};
+
+impl Zeroize for RsaPublicKey {
+    fn zeroize(&mut self) {
+        self.e.zeroize();
+        self.n.zeroize();
+    }
+}
+
+impl Zeroize for DsaPublicKey {
+    fn zeroize(&mut self) {
+        self.p.zeroize();
+        self.q.zeroize();
+        self.g.zeroize();
+        self.y.zeroize();
+    }
+}
+
+impl Zeroize for EcdsaPublicKey {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl Zeroize for Ed25519PublicKey {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl Zeroize for Ed448PublicKey {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl Zeroize for Ed25519SkPublicKey {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.application.zeroize();
+    }
+}
+
+impl Zeroize for EcdsaSkPublicKey {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.application.zeroize();
+    }
+}
+
+impl Zeroize for XmssPublicKey {
+    fn zeroize(&mut self) {
+        self.oid.zeroize();
+        self.key.zeroize();
+    }
+}
+
+impl Zeroize for PublicKeyKind {
+    fn zeroize(&mut self) {
+        match *self {
+            PublicKeyKind::Rsa(ref mut k) => k.zeroize(),
+            PublicKeyKind::Dsa(ref mut k) => k.zeroize(),
+            PublicKeyKind::Ecdsa(ref mut k) => k.zeroize(),
+            PublicKeyKind::Ed25519(ref mut k) => k.zeroize(),
+            PublicKeyKind::Ed448(ref mut k) => k.zeroize(),
+            PublicKeyKind::SkEd25519(ref mut k) => k.zeroize(),
+            PublicKeyKind::SkEcdsa(ref mut k) => k.zeroize(),
+            PublicKeyKind::Xmss(ref mut k) => k.zeroize(),
+        }
+    }
+}
+
+impl Zeroize for PublicKey {
+    fn zeroize(&mut self) {
+        self.kind.zeroize();
+        self.comment.zeroize();
+        if let Some(ref mut o) = self.original_blob {
+            o.blob.zeroize();
+            o.kind.zeroize();
+        }
+        self.original_blob = None;
+    }
+}
+
+impl Zeroize for EcdsaSignature {
+    fn zeroize(&mut self) {
+        self.r.zeroize();
+        self.s.zeroize();
+    }
+}
+
+impl Zeroize for SignatureKind {
+    fn zeroize(&mut self) {
+        match *self {
+            SignatureKind::Rsa(ref mut v)
+            | SignatureKind::Dsa(ref mut v)
+            | SignatureKind::Ed25519(ref mut v)
+            | SignatureKind::Ed448(ref mut v) => v.zeroize(),
+            SignatureKind::Ecdsa(ref mut s) => s.zeroize(),
+        }
+    }
+}
+
+impl Zeroize for Signature {
+    fn zeroize(&mut self) {
+        self.kind.zeroize();
+    }
+}
+
+// Clears the names and values of the given certificate options and removes them.
+// The names cannot be modified in place, so the options are moved out of the map first.
+fn zeroize_options(options: &mut BTreeMap<String, Vec<u8>>) {
+    let options = mem::take(options);
+    for (mut name, mut value) in options {
+        name.zeroize();
+        value.zeroize();
+    }
+}
+
+impl Zeroize for Certificate {
+    fn zeroize(&mut self) {
+        self.nonce.zeroize();
+        self.key.zeroize();
+        self.key_id.zeroize();
+        self.valid_principals.zeroize();
+        zeroize_options(&mut self.critical_options);
+        zeroize_options(&mut self.extensions);
+        self.reserved.zeroize();
+        self.signature_key.zeroize();
+        self.signature.zeroize();
+        self.comment.zeroize();
+        self.signed_data.zeroize();
+    }
+}
@@ -0,0 +1,97 @@
+extern crate sshkeys;
+
+// Encodes the payload of an `SSH2_AGENT_IDENTITIES_ANSWER` message.
+fn encode_identities_answer(count: u32, identities: &[(Vec<u8>, &str)]) -> Vec<u8> {
+    let mut writer = sshkeys::Writer::new();
+    writer.write_u8(12);
+    writer.write_u32(count);
+    for &(ref blob, comment) in identities {
+        writer.write_bytes(blob);
+        writer.write_string(comment);
+    }
+
+    writer.into_bytes()
+}
+
+#[test]
+fn test_parse_agent_identities() {
+    let rsa = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let ed25519 = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+
+    let payload = encode_identities_answer(
+        2,
+        &[
+            (rsa.encode(), "/home/me/.ssh/id_rsa"),
+            (ed25519.encode(), ""),
+        ],
+    );
+    let identities = sshkeys::parse_agent_identities(&payload).unwrap();
+
+    assert_eq!(identities.len(), 2);
+    assert_eq!(identities[0].0, rsa);
+    assert_eq!(identities[0].1, "/home/me/.ssh/id_rsa");
+    assert_eq!(identities[1].0, ed25519);
+    assert_eq!(identities[1].1, "");
+
+    let payload = encode_identities_answer(0, &[]);
+    assert!(sshkeys::parse_agent_identities(&payload)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_parse_agent_identities_invalid() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let error = |payload: &[u8]| match sshkeys::parse_agent_identities(payload) {
+        Ok(v) => panic!("Expected error, got {:?}", v),
+        Err(e) => e.to_string(),
+    };
+
+    // Wrong message number, e.g. SSH_AGENT_FAILURE
+    let mut payload = encode_identities_answer(1, &[(key.encode(), "me@home")]);
+    payload[0] = 5;
+    assert_eq!(error(&payload), "Invalid format");
+
+    // The count is larger than the number of identities
+    let payload = encode_identities_answer(2, &[(key.encode(), "me@home")]);
+    assert_eq!(
+        error(&payload),
+        format!(
+            "Unexpected EOF reached while reading 4 bytes at offset {}",
+            payload.len()
+        )
+    );
+
+    // The count is smaller than the number of identities
+    let payload =
+        encode_identities_answer(1, &[(key.encode(), "me@home"), (key.encode(), "me@work")]);
+    assert_eq!(error(&payload), "Trailing data after key");
+
+    // A huge count with no identities does not allocate
+    let payload = encode_identities_answer(u32::MAX, &[]);
+    assert!(sshkeys::parse_agent_identities(&payload).is_err());
+
+    assert!(sshkeys::parse_agent_identities(&[]).is_err());
+}
+
+#[test]
+fn test_pubkey_blob_framing() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_384.pub").unwrap();
+    let blob = key.encode();
+    assert_eq!(key.to_blob(), blob);
+    assert_eq!(blob.len(), key.encoded_len());
+
+    let prefixed = key.to_prefixed_blob();
+    assert_eq!(prefixed.len(), 4 + blob.len());
+
+    let mut reader = sshkeys::Reader::new(&prefixed);
+    assert_eq!(reader.read_bytes().unwrap(), blob);
+    assert_eq!(reader.remaining(), 0);
+
+    // Identities in agent messages consist of the prefixed blob and the comment
+    let mut payload = vec![12, 0, 0, 0, 1];
+    payload.extend_from_slice(&prefixed);
+    payload.extend_from_slice(&[0, 0, 0, 0]);
+    let identities = sshkeys::parse_agent_identities(&payload).unwrap();
+    assert_eq!(identities, vec![(key, String::new())]);
+}
@@ -0,0 +1,105 @@
+use std::fs::File;
+
+extern crate sshkeys;
+
+#[test]
+fn test_parse_authorized_keys() {
+    let file = File::open("tests/test-keys/authorized_keys").unwrap();
+    let entries = sshkeys::parse_authorized_keys(file).unwrap();
+
+    assert_eq!(entries.len(), 3);
+
+    let ed25519 = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    assert_eq!(entries[0].options, None);
+    assert_eq!(entries[0].options_list(), Vec::<&str>::new());
+    assert_eq!(entries[0].key, ed25519);
+    assert_eq!(entries[0].comment, Some("me@home".to_string()));
+
+    assert_eq!(
+        entries[1].options,
+        Some("no-pty,command=\"echo foo,bar\"".to_string())
+    );
+    assert_eq!(
+        entries[1].options_list(),
+        vec!["no-pty", "command=\"echo foo,bar\""]
+    );
+    assert_eq!(entries[1].key.key_type.kind, sshkeys::KeyTypeKind::Rsa);
+    assert_eq!(entries[1].comment, Some("backup key".to_string()));
+
+    assert_eq!(
+        entries[2].options_list(),
+        vec!["from=\"10.0.0.1,10.0.0.2\"", "no-agent-forwarding"]
+    );
+    assert_eq!(entries[2].key.key_type.kind, sshkeys::KeyTypeKind::Ecdsa);
+    assert_eq!(entries[2].comment, Some("me@home".to_string()));
+}
+
+#[test]
+fn test_authorized_key_quoted_whitespace() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let line = format!("command=\"echo \\\"a b\\\"\",no-pty {}", key);
+
+    let entry = sshkeys::AuthorizedKey::from_string(&line).unwrap();
+    assert_eq!(
+        entry.options_list(),
+        vec!["command=\"echo \\\"a b\\\"\"", "no-pty"]
+    );
+    assert_eq!(entry.key, key);
+}
+
+#[test]
+#[should_panic(expected = "Invalid format")]
+fn test_authorized_key_unterminated_quote() {
+    let line = "command=\"echo ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIMIVp6q5co/r5GwY0dH+NYQbfKicapeF3gXEU3dzaAvD";
+
+    match sshkeys::AuthorizedKey::from_string(line) {
+        Ok(v) => panic!("Expected invalid format, got {:?}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+fn test_parse_authorized_keys_crlf() {
+    let contents = "# comment\r\nssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home\r\n\r\nno-pty ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd\r\n";
+    let entries = sshkeys::parse_authorized_keys(contents.as_bytes()).unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].options, None);
+    assert_eq!(entries[0].comment, Some("me@home".to_string()));
+    assert_eq!(entries[1].options, Some("no-pty".to_string()));
+    assert_eq!(entries[1].comment, None);
+    assert_eq!(entries[0].key, entries[1].key);
+}
+
+#[test]
+fn test_parse_authorized_keys_lenient() {
+    let file = File::open("tests/test-keys/authorized_keys").unwrap();
+    let (entries, errors) = sshkeys::parse_authorized_keys_lenient(file);
+    assert_eq!(entries.len(), 3);
+    assert!(errors.is_empty());
+
+    let mut contents = Vec::new();
+    contents.extend_from_slice(b"# A comment\n");
+    contents.extend_from_slice(b"ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home\n");
+    contents.extend_from_slice(b"ssh-foo AAAA\n");
+    contents.extend_from_slice(b"\n");
+    contents.extend_from_slice(b"command=\"echo \xff\" ssh-ed25519 AAAA\n");
+    contents.extend_from_slice(b"no-pty,command=\"unterminated ssh-ed25519 AAAA\n");
+    contents.extend_from_slice(
+        b"no-pty ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd",
+    );
+
+    let (entries, errors) = sshkeys::parse_authorized_keys_lenient(&contents[..]);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].options, None);
+    assert_eq!(entries[1].options, Some("no-pty".to_string()));
+
+    let errors: Vec<(usize, String)> = errors
+        .into_iter()
+        .map(|(line, e)| (line, e.to_string()))
+        .collect();
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].0, 3);
+    assert_eq!(errors[1].0, 5);
+    assert_eq!(errors[2], (6, "Invalid format".to_string()));
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "bigint")]
+
+extern crate num_bigint;
+extern crate sshkeys;
+
+use num_bigint::BigUint;
+
+#[test]
+fn test_rsa_biguint() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let rsa = match key.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => k,
+        _ => panic!("Expected RSA public key"),
+    };
+
+    assert_eq!(rsa.exponent_biguint(), BigUint::from(65537u32));
+
+    // The modulus as printed by `openssl rsa -pubin -modulus`
+    let modulus = rsa.modulus_biguint();
+    assert_eq!(modulus.bits(), 2048);
+    assert!(format!("{:X}", modulus)
+        .starts_with("A20A18A71FDBE895AB93BCB7C65AFDB1088A348EB400BF48C242"));
+    assert_eq!(modulus.to_bytes_be(), rsa.n);
+}
+
+#[test]
+fn test_dsa_biguint() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_dsa_1024.pub").unwrap();
+    let dsa = match key.kind {
+        sshkeys::PublicKeyKind::Dsa(ref k) => k,
+        _ => panic!("Expected DSA public key"),
+    };
+
+    assert_eq!(dsa.p_biguint().bits(), 1024);
+    assert_eq!(dsa.q_biguint().bits(), 160);
+    assert_eq!(dsa.g_biguint().to_bytes_be(), dsa.g);
+    assert_eq!(dsa.y_biguint().to_bytes_be(), dsa.y);
+}
+
+#[test]
+fn test_reader_read_biguint() {
+    let data = [
+        0, 0, 0, 0, // zero
+        0, 0, 0, 2, 0x7f, 0xff, // no leading zero byte needed
+        0, 0, 0, 3, 0, 0x80, 0, // leading zero byte marks a positive number
+        0, 0, 0, 3, 0, 0, 1, // redundant leading zero bytes
+    ];
+    let mut reader = sshkeys::Reader::new(&data);
+
+    assert_eq!(reader.read_biguint().unwrap(), BigUint::from(0u32));
+    assert_eq!(reader.read_biguint().unwrap(), BigUint::from(0x7fffu32));
+    assert_eq!(reader.read_biguint().unwrap(), BigUint::from(0x8000u32));
+    assert_eq!(reader.read_biguint().unwrap(), BigUint::from(1u32));
+}
+
+#[test]
+fn test_reader_read_biguint_negative() {
+    let data = [0, 0, 0, 2, 0x80, 0];
+    let mut reader = sshkeys::Reader::new(&data);
+
+    match reader.read_biguint() {
+        Ok(v) => panic!("Expected error for negative mpint, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Invalid format"),
+    }
+}
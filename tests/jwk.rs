@@ -0,0 +1,158 @@
+#![cfg(feature = "jwk")]
+
+extern crate base64;
+extern crate sshkeys;
+
+fn b64url(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+#[test]
+fn test_from_jwk_rfc_examples() {
+    // RFC 8037, appendix A.2
+    let jwk = r#"{"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#;
+    let key = sshkeys::PublicKey::from_jwk(jwk).unwrap();
+    assert_eq!(key.key_type.name, "ssh-ed25519");
+    assert_eq!(
+        key.key_data(),
+        base64::decode_config(
+            "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo",
+            base64::URL_SAFE_NO_PAD
+        )
+        .unwrap()
+    );
+
+    // RFC 7517, appendix A.1
+    let jwk = r#"{
+        "kty": "EC",
+        "crv": "P-256",
+        "x": "MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4",
+        "y": "4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM",
+        "use": "enc",
+        "kid": "1"
+    }"#;
+    let key = sshkeys::PublicKey::from_jwk(jwk).unwrap();
+    assert_eq!(key.key_type.name, "ecdsa-sha2-nistp256");
+    assert_eq!(key.bits(), 256);
+    assert!(key.validate().is_ok());
+}
+
+#[test]
+fn test_from_jwk_rsa() {
+    let expected = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let (e, n) = match expected.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => (k.e.clone(), k.n.clone()),
+        _ => panic!("Expected RSA public key"),
+    };
+
+    let jwk = format!(
+        r#"{{"kty": "RSA", "n": "{}", "e": "{}"}}"#,
+        b64url(&n),
+        b64url(&e)
+    );
+    let key = sshkeys::PublicKey::from_jwk(&jwk).unwrap();
+    assert_eq!(key, expected);
+    assert_eq!(key.comment, None);
+}
+
+#[test]
+fn test_from_jwk_ecdsa() {
+    let keys = [
+        ("tests/test-keys/id_ecdsa_256.pub", "P-256", 32),
+        ("tests/test-keys/id_ecdsa_384.pub", "P-384", 48),
+        ("tests/test-keys/id_ecdsa_521.pub", "P-521", 66),
+    ];
+
+    for (path, crv, len) in keys.iter() {
+        let expected = sshkeys::PublicKey::from_path(path).unwrap();
+        let point = expected.key_data();
+        let (x, y) = (&point[1..len + 1], &point[len + 1..]);
+
+        let jwk = format!(
+            r#"{{"kty": "EC", "crv": "{}", "x": "{}", "y": "{}"}}"#,
+            crv,
+            b64url(x),
+            b64url(y)
+        );
+        assert_eq!(sshkeys::PublicKey::from_jwk(&jwk).unwrap(), expected);
+
+        // Short coordinates are padded with leading zero bytes
+        let jwk = format!(
+            r#"{{"kty": "EC", "crv": "{}", "x": "{}", "y": "{}"}}"#,
+            crv,
+            b64url(&x[2..]),
+            b64url(y)
+        );
+        let mut padded = point.clone();
+        padded[1] = 0;
+        padded[2] = 0;
+        assert_eq!(
+            sshkeys::PublicKey::from_jwk(&jwk).unwrap().key_data(),
+            padded
+        );
+    }
+}
+
+#[test]
+fn test_from_jwk_ed25519() {
+    let expected = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let jwk = format!(
+        r#"{{"kty": "OKP", "crv": "Ed25519", "x": "{}"}}"#,
+        b64url(&expected.key_data())
+    );
+    assert_eq!(sshkeys::PublicKey::from_jwk(&jwk).unwrap(), expected);
+}
+
+#[test]
+fn test_from_jwk_invalid() {
+    let x = "eRt7uDDHuzR2UCkQTOlTI4A-vM8vLhzMRnD-yHybyt0";
+    let cases = [
+        ("not json".to_string(), "Invalid format"),
+        ("[]".to_string(), "Invalid format"),
+        (
+            format!(r#"{{"crv": "Ed25519", "x": "{}"}}"#, x),
+            "Invalid format",
+        ),
+        (
+            format!(r#"{{"kty": "oct", "crv": "Ed25519", "x": "{}"}}"#, x),
+            "Unknown key type oct",
+        ),
+        (
+            format!(r#"{{"kty": "OKP", "crv": "X25519", "x": "{}"}}"#, x),
+            "Unknown curve X25519",
+        ),
+        (
+            format!(r#"{{"kty": "OKP", "crv": "Ed448", "x": "{}"}}"#, x),
+            "Invalid format",
+        ),
+        (
+            format!(
+                r#"{{"kty": "EC", "crv": "P-192", "x": "{}", "y": "{}"}}"#,
+                x, x
+            ),
+            "Unknown curve P-192",
+        ),
+        (
+            format!(r#"{{"kty": "EC", "crv": "P-256", "x": "{}"}}"#, x),
+            "Invalid format",
+        ),
+        (
+            format!(
+                r#"{{"kty": "EC", "crv": "P-256", "x": "{}A", "y": "{}"}}"#,
+                x, x
+            ),
+            "Invalid format",
+        ),
+        (
+            r#"{"kty": "RSA", "n": 42, "e": "AQAB"}"#.to_string(),
+            "Invalid format",
+        ),
+    ];
+
+    for (jwk, error) in cases.iter() {
+        match sshkeys::PublicKey::from_jwk(jwk) {
+            Ok(v) => panic!("Expected error for {}, got {:?}", jwk, v),
+            Err(e) => assert_eq!(e.to_string(), *error, "{}", jwk),
+        }
+    }
+}
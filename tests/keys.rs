@@ -1,7 +1,17 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs;
 
+extern crate base64;
 extern crate sshkeys;
 
+// Encodes the value of a `string` certificate option.
+fn option_value(value: &str) -> Vec<u8> {
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string(value);
+    writer.into_bytes()
+}
+
 #[test]
 fn test_rsa_pubkey_1024() {
     let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_1024.pub").unwrap();
@@ -9,7 +19,7 @@ fn test_rsa_pubkey_1024() {
     assert_eq!(key.key_type.name, "ssh-rsa");
     assert_eq!(key.key_type.plain, "ssh-rsa");
     assert_eq!(key.key_type.short_name, "RSA");
-    assert_eq!(key.key_type.is_cert, false);
+    assert!(!key.key_type.is_cert);
     assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Rsa);
 
     assert_eq!(key.bits(), 1024);
@@ -25,15 +35,18 @@ fn test_rsa_pubkey_1024() {
     let sha512fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "izTlwvAwZNoPhsSHPFvSWBx7mAnX0regyVjXfQTMv6Y");
+    assert_eq!(
+        sha256fp.hash(),
+        "izTlwvAwZNoPhsSHPFvSWBx7mAnX0regyVjXfQTMv6Y"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "dBi3NL7zSWb1zsQob8ROuRggCtkr6n60VbIy+Io4iYil4UIieUvcco03TWpjdv/u"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "0GhrWC58WCwoXXE5mfmKBeLdEjwH2Xzg1Z3K7n5mBtLmcTu+OeIOw9bJJ2FPuskz57Bu2dJvOFkGidw2RW4fvg"
     );
 }
@@ -45,7 +58,7 @@ fn test_rsa_pubkey_2048() {
     assert_eq!(key.key_type.name, "ssh-rsa");
     assert_eq!(key.key_type.plain, "ssh-rsa");
     assert_eq!(key.key_type.short_name, "RSA");
-    assert_eq!(key.key_type.is_cert, false);
+    assert!(!key.key_type.is_cert);
     assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Rsa);
 
     assert_eq!(key.bits(), 2048);
@@ -61,15 +74,18 @@ fn test_rsa_pubkey_2048() {
     let sha512fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "5mDozobgKuNO6/FutOgATBvGfYQbNfBlUY6iBYSdqF0");
+    assert_eq!(
+        sha256fp.hash(),
+        "5mDozobgKuNO6/FutOgATBvGfYQbNfBlUY6iBYSdqF0"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "dgNFIE9GNNznHqdnL7Ml1CScn5X/5NAT2tpSqd6NWGhXPU3o1rz3SMKyELzuuArv"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "FDoxtx0ir1FlZUkHjUugzNZE7Qi3lJaUkN9QabPulm6/MXAcXXIhsW5C/mJaCDY1hDbeoo39aqHcTO+MdQiJsQ"
     );
 }
@@ -99,14 +115,14 @@ fn test_rsa_user_cert() {
     assert_eq!(cert.key_type.name, "ssh-rsa-cert-v01@openssh.com");
     assert_eq!(cert.key_type.plain, "ssh-rsa");
     assert_eq!(cert.key_type.short_name, "RSA-CERT");
-    assert_eq!(cert.key_type.is_cert, true);
+    assert!(cert.key_type.is_cert);
     assert_eq!(cert.key_type.kind, sshkeys::KeyTypeKind::RsaCert);
 
     // Public key part of the certificate
     assert_eq!(cert.key.key_type.name, "ssh-rsa-cert-v01@openssh.com");
     assert_eq!(cert.key.key_type.plain, "ssh-rsa");
     assert_eq!(cert.key.key_type.short_name, "RSA-CERT");
-    assert_eq!(cert.key.key_type.is_cert, true);
+    assert!(cert.key.key_type.is_cert);
     assert_eq!(cert.key.key_type.kind, sshkeys::KeyTypeKind::RsaCert);
     assert_eq!(cert.key.bits(), 2048);
     assert_eq!(cert.key.comment, None);
@@ -117,15 +133,18 @@ fn test_rsa_user_cert() {
     let sha512fp = cert.key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "5mDozobgKuNO6/FutOgATBvGfYQbNfBlUY6iBYSdqF0");
+    assert_eq!(
+        sha256fp.hash(),
+        "5mDozobgKuNO6/FutOgATBvGfYQbNfBlUY6iBYSdqF0"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "dgNFIE9GNNznHqdnL7Ml1CScn5X/5NAT2tpSqd6NWGhXPU3o1rz3SMKyELzuuArv"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "FDoxtx0ir1FlZUkHjUugzNZE7Qi3lJaUkN9QabPulm6/MXAcXXIhsW5C/mJaCDY1hDbeoo39aqHcTO+MdQiJsQ"
     );
 
@@ -136,27 +155,27 @@ fn test_rsa_user_cert() {
     assert_eq!(cert.valid_after, 1505374860);
     assert_eq!(cert.valid_before, 1536824561);
 
-    let mut co = HashMap::new();
-    co.insert("force-command".to_string(), "/usr/bin/true".to_string());
-    co.insert("source-address".to_string(), "127.0.0.1".to_string());
+    let mut co = BTreeMap::new();
+    co.insert("force-command".to_string(), option_value("/usr/bin/true"));
+    co.insert("source-address".to_string(), option_value("127.0.0.1"));
     assert_eq!(cert.critical_options, co);
 
-    let mut extensions = HashMap::new();
-    extensions.insert("permit-X11-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-agent-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-port-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-pty".to_string(), "".to_string());
-    extensions.insert("permit-user-rc".to_string(), "".to_string());
+    let mut extensions = BTreeMap::new();
+    extensions.insert("permit-X11-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-agent-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-port-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-pty".to_string(), Vec::new());
+    extensions.insert("permit-user-rc".to_string(), Vec::new());
     assert_eq!(cert.extensions, extensions);
 
     // The `reserved` field is empty in the current implementation of OpenSSH certificates
-    assert_eq!(cert.reserved, Vec::new());
+    assert_eq!(cert.reserved, Vec::<u8>::new());
 
     // CA public key
     assert_eq!(cert.signature_key.key_type.name, "ssh-rsa");
     assert_eq!(cert.signature_key.key_type.plain, "ssh-rsa");
     assert_eq!(cert.signature_key.key_type.short_name, "RSA");
-    assert_eq!(cert.signature_key.key_type.is_cert, false);
+    assert!(!cert.signature_key.key_type.is_cert);
     assert_eq!(cert.signature_key.key_type.kind, sshkeys::KeyTypeKind::Rsa);
     assert_eq!(cert.signature_key.bits(), 2048);
     assert_eq!(cert.signature_key.comment, None);
@@ -173,15 +192,18 @@ fn test_rsa_user_cert() {
         .fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s");
+    assert_eq!(
+        sha256fp.hash(),
+        "8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "7+2ZLPaqbntHUtypie8404NhIIqgo9b6/XWNABjgTphWic38/EDYXYm35SLllIxm"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "BrQgwbsBLlnyiOGITMfl+H2I7HCcCYiy22Hx0j62bWvifyZLyGA5PIoId+846U1P31cMX77l9Ok0qh9meltGCw"
     );
 
@@ -204,7 +226,7 @@ fn test_dsa_pubkey_1024() {
     assert_eq!(key.key_type.name, "ssh-dss");
     assert_eq!(key.key_type.plain, "ssh-dss");
     assert_eq!(key.key_type.short_name, "DSA");
-    assert_eq!(key.key_type.is_cert, false);
+    assert!(!key.key_type.is_cert);
     assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Dsa);
 
     assert_eq!(key.bits(), 1024);
@@ -220,15 +242,18 @@ fn test_dsa_pubkey_1024() {
     let sha512fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "i+5TCv/r9PXHeJMeGbgH8xfpgbGsTFFKapQudFR2aFQ");
+    assert_eq!(
+        sha256fp.hash(),
+        "i+5TCv/r9PXHeJMeGbgH8xfpgbGsTFFKapQudFR2aFQ"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "m55cGSOiyn+U2mJX7sLOK/hcwDQmh16YhC5/ibhc2tnn8OOin4sXgpBRY6ZLXL/j"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "nNCtXgIQx+DZTUQPDVEVIl5SObBlD4MiJzBiFUsuNGPnjoF22kQeQkGYCioWfGQBrUR33p9/1jXLHfpHETuYSw"
     );
 }
@@ -240,14 +265,14 @@ fn test_dsa_user_cert() {
     assert_eq!(cert.key_type.name, "ssh-dss-cert-v01@openssh.com");
     assert_eq!(cert.key_type.plain, "ssh-dss");
     assert_eq!(cert.key_type.short_name, "DSA-CERT");
-    assert_eq!(cert.key_type.is_cert, true);
+    assert!(cert.key_type.is_cert);
     assert_eq!(cert.key_type.kind, sshkeys::KeyTypeKind::DsaCert);
 
     // Public key part of the certificate
     assert_eq!(cert.key.key_type.name, "ssh-dss-cert-v01@openssh.com");
     assert_eq!(cert.key.key_type.plain, "ssh-dss");
     assert_eq!(cert.key.key_type.short_name, "DSA-CERT");
-    assert_eq!(cert.key.key_type.is_cert, true);
+    assert!(cert.key.key_type.is_cert);
     assert_eq!(cert.key.key_type.kind, sshkeys::KeyTypeKind::DsaCert);
     assert_eq!(cert.key.bits(), 1024);
     assert_eq!(cert.key.comment, None);
@@ -257,15 +282,18 @@ fn test_dsa_user_cert() {
     let sha512fp = cert.key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "i+5TCv/r9PXHeJMeGbgH8xfpgbGsTFFKapQudFR2aFQ");
+    assert_eq!(
+        sha256fp.hash(),
+        "i+5TCv/r9PXHeJMeGbgH8xfpgbGsTFFKapQudFR2aFQ"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "m55cGSOiyn+U2mJX7sLOK/hcwDQmh16YhC5/ibhc2tnn8OOin4sXgpBRY6ZLXL/j"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "nNCtXgIQx+DZTUQPDVEVIl5SObBlD4MiJzBiFUsuNGPnjoF22kQeQkGYCioWfGQBrUR33p9/1jXLHfpHETuYSw"
     );
 
@@ -277,27 +305,27 @@ fn test_dsa_user_cert() {
     assert_eq!(cert.valid_after, 1505475180);
     assert_eq!(cert.valid_before, 1536924895);
 
-    let mut co = HashMap::new();
-    co.insert("force-command".to_string(), "/usr/bin/true".to_string());
-    co.insert("source-address".to_string(), "127.0.0.1".to_string());
+    let mut co = BTreeMap::new();
+    co.insert("force-command".to_string(), option_value("/usr/bin/true"));
+    co.insert("source-address".to_string(), option_value("127.0.0.1"));
     assert_eq!(cert.critical_options, co);
 
-    let mut extensions = HashMap::new();
-    extensions.insert("permit-X11-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-agent-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-port-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-pty".to_string(), "".to_string());
-    extensions.insert("permit-user-rc".to_string(), "".to_string());
+    let mut extensions = BTreeMap::new();
+    extensions.insert("permit-X11-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-agent-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-port-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-pty".to_string(), Vec::new());
+    extensions.insert("permit-user-rc".to_string(), Vec::new());
     assert_eq!(cert.extensions, extensions);
 
     // The `reserved` field is empty in the current implementation of OpenSSH certificates
-    assert_eq!(cert.reserved, Vec::new());
+    assert_eq!(cert.reserved, Vec::<u8>::new());
 
     // CA public key
     assert_eq!(cert.signature_key.key_type.name, "ssh-rsa");
     assert_eq!(cert.signature_key.key_type.plain, "ssh-rsa");
     assert_eq!(cert.signature_key.key_type.short_name, "RSA");
-    assert_eq!(cert.signature_key.key_type.is_cert, false);
+    assert!(!cert.signature_key.key_type.is_cert);
     assert_eq!(cert.signature_key.key_type.kind, sshkeys::KeyTypeKind::Rsa);
     assert_eq!(cert.signature_key.bits(), 2048);
     assert_eq!(cert.signature_key.comment, None);
@@ -314,15 +342,18 @@ fn test_dsa_user_cert() {
         .fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s");
+    assert_eq!(
+        sha256fp.hash(),
+        "8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "7+2ZLPaqbntHUtypie8404NhIIqgo9b6/XWNABjgTphWic38/EDYXYm35SLllIxm"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "BrQgwbsBLlnyiOGITMfl+H2I7HCcCYiy22Hx0j62bWvifyZLyGA5PIoId+846U1P31cMX77l9Ok0qh9meltGCw"
     );
 
@@ -336,7 +367,7 @@ pub fn test_ecdsa_nistp256_pubkey() {
     assert_eq!(key.key_type.name, "ecdsa-sha2-nistp256");
     assert_eq!(key.key_type.plain, "ecdsa-sha2-nistp256");
     assert_eq!(key.key_type.short_name, "ECDSA");
-    assert_eq!(key.key_type.is_cert, false);
+    assert!(!key.key_type.is_cert);
     assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Ecdsa);
 
     assert_eq!(key.bits(), 256);
@@ -347,15 +378,18 @@ pub fn test_ecdsa_nistp256_pubkey() {
     let sha512fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "RiRAmX+9kOD9dgFhocPtQi726sZXbQ2RmrkXevu6Avg");
+    assert_eq!(
+        sha256fp.hash(),
+        "RiRAmX+9kOD9dgFhocPtQi726sZXbQ2RmrkXevu6Avg"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "fM0Czmf55Od4g4zbLZueLFnbwFr0DmJQytpB7Xb2kjG6diar/7CskhVUkfX43fh6"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "8qXVmeSbYWN6D79reref2iz+tadg68qpkJDG0Z6B6u4U7XK0C3vYrDQVHg38FUKxvzAkw0c2gOYXqhP1RYo+Fw"
     );
 
@@ -375,7 +409,7 @@ pub fn test_ecdsa_nistp384_pubkey() {
     assert_eq!(key.key_type.name, "ecdsa-sha2-nistp384");
     assert_eq!(key.key_type.plain, "ecdsa-sha2-nistp384");
     assert_eq!(key.key_type.short_name, "ECDSA");
-    assert_eq!(key.key_type.is_cert, false);
+    assert!(!key.key_type.is_cert);
     assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Ecdsa);
 
     assert_eq!(key.bits(), 384);
@@ -386,15 +420,18 @@ pub fn test_ecdsa_nistp384_pubkey() {
     let sha512fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "XyWmNHs59uQcNJBv6Iq6sbDAa5/u2GD1Nyu2YHcS2jQ");
+    assert_eq!(
+        sha256fp.hash(),
+        "XyWmNHs59uQcNJBv6Iq6sbDAa5/u2GD1Nyu2YHcS2jQ"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "YXnQ8c1kDAQirgRgHSwswvT6zOFmvbvwL8au771Ska7+arFQgMe5Se9LPXeKmIWR"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "p73av0cbNsWXLexTQNpUxjGE4k+on8IrwsmIJP7xUhf7s1irVTBCpLA0wJ44IbMzUvMLuIj/FtoV1nTilYpb3w"
     );
 
@@ -414,7 +451,7 @@ pub fn test_ecdsa_nistp521_pubkey() {
     assert_eq!(key.key_type.name, "ecdsa-sha2-nistp521");
     assert_eq!(key.key_type.plain, "ecdsa-sha2-nistp521");
     assert_eq!(key.key_type.short_name, "ECDSA");
-    assert_eq!(key.key_type.is_cert, false);
+    assert!(!key.key_type.is_cert);
     assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Ecdsa);
 
     assert_eq!(key.bits(), 521);
@@ -425,15 +462,18 @@ pub fn test_ecdsa_nistp521_pubkey() {
     let sha512fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "kEdMLsbAeJPDv3mEwIchjSxkcL/+XFzI9u1NHCWbsT8");
+    assert_eq!(
+        sha256fp.hash(),
+        "kEdMLsbAeJPDv3mEwIchjSxkcL/+XFzI9u1NHCWbsT8"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "ZD2U1VncXLttbPAEMtUX/rCl4JtgxI1XJOYPXeP7EzzBeXr3KVVb4Wn/u/Qp4i0Q"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "4EI3hnZ0KhIa0Sp8Z1CWWL8I0t8DaSs4+E8jiLFRAZ+EUeFYPysy6SrCbMDgSk5sfo3+2UA5SVqnZtBdmVQeIg"
     );
 
@@ -456,7 +496,7 @@ fn test_ecdsa_user_cert() {
     );
     assert_eq!(cert.key_type.plain, "ecdsa-sha2-nistp521");
     assert_eq!(cert.key_type.short_name, "ECDSA-CERT");
-    assert_eq!(cert.key_type.is_cert, true);
+    assert!(cert.key_type.is_cert);
     assert_eq!(cert.key_type.kind, sshkeys::KeyTypeKind::EcdsaCert);
 
     // Public key part of the certificate
@@ -466,7 +506,7 @@ fn test_ecdsa_user_cert() {
     );
     assert_eq!(cert.key.key_type.plain, "ecdsa-sha2-nistp521");
     assert_eq!(cert.key.key_type.short_name, "ECDSA-CERT");
-    assert_eq!(cert.key.key_type.is_cert, true);
+    assert!(cert.key.key_type.is_cert);
     assert_eq!(cert.key.key_type.kind, sshkeys::KeyTypeKind::EcdsaCert);
     assert_eq!(cert.key.bits(), 521);
     assert_eq!(cert.key.comment, None);
@@ -475,15 +515,18 @@ fn test_ecdsa_user_cert() {
     let sha384fp = cert.key.fingerprint_with(sshkeys::FingerprintKind::Sha384);
     let sha512fp = cert.key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "kEdMLsbAeJPDv3mEwIchjSxkcL/+XFzI9u1NHCWbsT8");
+    assert_eq!(
+        sha256fp.hash(),
+        "kEdMLsbAeJPDv3mEwIchjSxkcL/+XFzI9u1NHCWbsT8"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "ZD2U1VncXLttbPAEMtUX/rCl4JtgxI1XJOYPXeP7EzzBeXr3KVVb4Wn/u/Qp4i0Q"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "4EI3hnZ0KhIa0Sp8Z1CWWL8I0t8DaSs4+E8jiLFRAZ+EUeFYPysy6SrCbMDgSk5sfo3+2UA5SVqnZtBdmVQeIg"
     );
 
@@ -495,26 +538,26 @@ fn test_ecdsa_user_cert() {
     assert_eq!(cert.valid_after, 1506340920);
     assert_eq!(cert.valid_before, 1537790635);
 
-    let mut co = HashMap::new();
-    co.insert("source-address".to_string(), "127.0.0.1".to_string());
+    let mut co = BTreeMap::new();
+    co.insert("source-address".to_string(), option_value("127.0.0.1"));
     assert_eq!(cert.critical_options, co);
 
-    let mut extensions = HashMap::new();
-    extensions.insert("permit-X11-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-agent-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-port-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-pty".to_string(), "".to_string());
-    extensions.insert("permit-user-rc".to_string(), "".to_string());
+    let mut extensions = BTreeMap::new();
+    extensions.insert("permit-X11-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-agent-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-port-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-pty".to_string(), Vec::new());
+    extensions.insert("permit-user-rc".to_string(), Vec::new());
     assert_eq!(cert.extensions, extensions);
 
     // The `reserved` field is empty in the current implementation of OpenSSH certificates
-    assert_eq!(cert.reserved, Vec::new());
+    assert_eq!(cert.reserved, Vec::<u8>::new());
 
     // CA public key
     assert_eq!(cert.signature_key.key_type.name, "ssh-rsa");
     assert_eq!(cert.signature_key.key_type.plain, "ssh-rsa");
     assert_eq!(cert.signature_key.key_type.short_name, "RSA");
-    assert_eq!(cert.signature_key.key_type.is_cert, false);
+    assert!(!cert.signature_key.key_type.is_cert);
     assert_eq!(cert.signature_key.key_type.kind, sshkeys::KeyTypeKind::Rsa);
     assert_eq!(cert.signature_key.bits(), 2048);
     assert_eq!(cert.signature_key.comment, None);
@@ -529,15 +572,18 @@ fn test_ecdsa_user_cert() {
         .signature_key
         .fingerprint_with(sshkeys::FingerprintKind::Sha512);
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s");
+    assert_eq!(
+        sha256fp.hash(),
+        "8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "7+2ZLPaqbntHUtypie8404NhIIqgo9b6/XWNABjgTphWic38/EDYXYm35SLllIxm"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "BrQgwbsBLlnyiOGITMfl+H2I7HCcCYiy22Hx0j62bWvifyZLyGA5PIoId+846U1P31cMX77l9Ok0qh9meltGCw"
     );
 
@@ -551,7 +597,7 @@ pub fn test_ed25519_pubkey() {
     assert_eq!(key.key_type.name, "ssh-ed25519");
     assert_eq!(key.key_type.plain, "ssh-ed25519");
     assert_eq!(key.key_type.short_name, "ED25519");
-    assert_eq!(key.key_type.is_cert, false);
+    assert!(!key.key_type.is_cert);
     assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Ed25519);
 
     assert_eq!(key.bits(), 256);
@@ -562,15 +608,18 @@ pub fn test_ed25519_pubkey() {
     let sha512fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "ppYFPx0k4Ogs230n6eX9vGPpnNsTB0LPrDWXh1YjClA");
+    assert_eq!(
+        sha256fp.hash(),
+        "ppYFPx0k4Ogs230n6eX9vGPpnNsTB0LPrDWXh1YjClA"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "B4spD+NiA6esYoqr6dx+w0wBI3p3rQJsTku1rXIWGXTO87W1vvTKMFpwUOdNST2h"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "ljOfAT2lmNZbMDGNwNiLH/dPFIu+euUdXHP+5m0IobCBFYdg7mv8ltqtDBP2vP9vUcOWOow90EQoTPR4oZR1Nw"
     );
 
@@ -591,14 +640,14 @@ pub fn test_ed25519_user_cert() {
     assert_eq!(cert.key_type.name, "ssh-ed25519-cert-v01@openssh.com");
     assert_eq!(cert.key_type.plain, "ssh-ed25519");
     assert_eq!(cert.key_type.short_name, "ED25519-CERT");
-    assert_eq!(cert.key_type.is_cert, true);
+    assert!(cert.key_type.is_cert);
     assert_eq!(cert.key_type.kind, sshkeys::KeyTypeKind::Ed25519Cert);
 
     // Public key part of the certificate
     assert_eq!(cert.key.key_type.name, "ssh-ed25519-cert-v01@openssh.com");
     assert_eq!(cert.key.key_type.plain, "ssh-ed25519");
     assert_eq!(cert.key.key_type.short_name, "ED25519-CERT");
-    assert_eq!(cert.key.key_type.is_cert, true);
+    assert!(cert.key.key_type.is_cert);
     assert_eq!(cert.key.key_type.kind, sshkeys::KeyTypeKind::Ed25519Cert);
     assert_eq!(cert.key.bits(), 256);
     assert_eq!(cert.key.comment, None);
@@ -608,15 +657,18 @@ pub fn test_ed25519_user_cert() {
     let sha512fp = cert.key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "ppYFPx0k4Ogs230n6eX9vGPpnNsTB0LPrDWXh1YjClA");
+    assert_eq!(
+        sha256fp.hash(),
+        "ppYFPx0k4Ogs230n6eX9vGPpnNsTB0LPrDWXh1YjClA"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "B4spD+NiA6esYoqr6dx+w0wBI3p3rQJsTku1rXIWGXTO87W1vvTKMFpwUOdNST2h"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "ljOfAT2lmNZbMDGNwNiLH/dPFIu+euUdXHP+5m0IobCBFYdg7mv8ltqtDBP2vP9vUcOWOow90EQoTPR4oZR1Nw"
     );
 
@@ -628,26 +680,26 @@ pub fn test_ed25519_user_cert() {
     assert_eq!(cert.valid_after, 1506934140);
     assert_eq!(cert.valid_before, 1538383841);
 
-    let mut co = HashMap::new();
-    co.insert("force-command".to_string(), "/usr/bin/true".to_string());
+    let mut co = BTreeMap::new();
+    co.insert("force-command".to_string(), option_value("/usr/bin/true"));
     assert_eq!(cert.critical_options, co);
 
-    let mut extensions = HashMap::new();
-    extensions.insert("permit-X11-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-agent-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-port-forwarding".to_string(), "".to_string());
-    extensions.insert("permit-pty".to_string(), "".to_string());
-    extensions.insert("permit-user-rc".to_string(), "".to_string());
+    let mut extensions = BTreeMap::new();
+    extensions.insert("permit-X11-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-agent-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-port-forwarding".to_string(), Vec::new());
+    extensions.insert("permit-pty".to_string(), Vec::new());
+    extensions.insert("permit-user-rc".to_string(), Vec::new());
     assert_eq!(cert.extensions, extensions);
 
     // The `reserved` field is empty in the current implementation of OpenSSH certificates
-    assert_eq!(cert.reserved, Vec::new());
+    assert_eq!(cert.reserved, Vec::<u8>::new());
 
     // CA public key
     assert_eq!(cert.signature_key.key_type.name, "ssh-rsa");
     assert_eq!(cert.signature_key.key_type.plain, "ssh-rsa");
     assert_eq!(cert.signature_key.key_type.short_name, "RSA");
-    assert_eq!(cert.signature_key.key_type.is_cert, false);
+    assert!(!cert.signature_key.key_type.is_cert);
     assert_eq!(cert.signature_key.key_type.kind, sshkeys::KeyTypeKind::Rsa);
     assert_eq!(cert.signature_key.bits(), 2048);
     assert_eq!(cert.signature_key.comment, None);
@@ -662,15 +714,18 @@ pub fn test_ed25519_user_cert() {
         .signature_key
         .fingerprint_with(sshkeys::FingerprintKind::Sha512);
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s");
+    assert_eq!(
+        sha256fp.hash(),
+        "8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "7+2ZLPaqbntHUtypie8404NhIIqgo9b6/XWNABjgTphWic38/EDYXYm35SLllIxm"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "BrQgwbsBLlnyiOGITMfl+H2I7HCcCYiy22Hx0j62bWvifyZLyGA5PIoId+846U1P31cMX77l9Ok0qh9meltGCw"
     );
 
@@ -684,14 +739,14 @@ fn test_ed25519_host_cert() {
     assert_eq!(cert.key_type.name, "ssh-ed25519-cert-v01@openssh.com");
     assert_eq!(cert.key_type.plain, "ssh-ed25519");
     assert_eq!(cert.key_type.short_name, "ED25519-CERT");
-    assert_eq!(cert.key_type.is_cert, true);
+    assert!(cert.key_type.is_cert);
     assert_eq!(cert.key_type.kind, sshkeys::KeyTypeKind::Ed25519Cert);
 
     // Public key part of the certificate
     assert_eq!(cert.key.key_type.name, "ssh-ed25519-cert-v01@openssh.com");
     assert_eq!(cert.key.key_type.plain, "ssh-ed25519");
     assert_eq!(cert.key.key_type.short_name, "ED25519-CERT");
-    assert_eq!(cert.key.key_type.is_cert, true);
+    assert!(cert.key.key_type.is_cert);
     assert_eq!(cert.key.key_type.kind, sshkeys::KeyTypeKind::Ed25519Cert);
     assert_eq!(cert.key.bits(), 256);
     assert_eq!(cert.key.comment, None);
@@ -701,15 +756,18 @@ fn test_ed25519_host_cert() {
     let sha512fp = cert.key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
 
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "kkaqMnJz4XAhwz7n7Ov8RbHEYIJ8sxyGQWDmM5Ckot0");
+    assert_eq!(
+        sha256fp.hash(),
+        "kkaqMnJz4XAhwz7n7Ov8RbHEYIJ8sxyGQWDmM5Ckot0"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "vGjciz1R26zOHfZ8Vv8m2O7Cz7HxHKWbfuev/LbznWlOWuAqLl1QuuDk/oqhSxKr"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "NCUwuFl6hLiLLX9TUVwmaLjD5q4ql1ayGciFBklt3GYdQzLpX8sLMMBEgcrUgEfZjQtF18d3mNWbEx/okW6Vqw"
     );
 
@@ -722,21 +780,21 @@ fn test_ed25519_host_cert() {
     assert_eq!(cert.valid_before, 1538385716);
 
     // No critical options are defined for host certificates
-    let co = HashMap::new();
+    let co = BTreeMap::new();
     assert_eq!(cert.critical_options, co);
 
     // No extensions are defined for host certificates
-    let extensions = HashMap::new();
+    let extensions = BTreeMap::new();
     assert_eq!(cert.extensions, extensions);
 
     // The `reserved` field is empty in the current implementation of OpenSSH certificates
-    assert_eq!(cert.reserved, Vec::new());
+    assert_eq!(cert.reserved, Vec::<u8>::new());
 
     // CA public key
     assert_eq!(cert.signature_key.key_type.name, "ssh-ed25519");
     assert_eq!(cert.signature_key.key_type.plain, "ssh-ed25519");
     assert_eq!(cert.signature_key.key_type.short_name, "ED25519");
-    assert_eq!(cert.signature_key.key_type.is_cert, false);
+    assert!(!cert.signature_key.key_type.is_cert);
     assert_eq!(
         cert.signature_key.key_type.kind,
         sshkeys::KeyTypeKind::Ed25519
@@ -754,17 +812,2772 @@ fn test_ed25519_host_cert() {
         .signature_key
         .fingerprint_with(sshkeys::FingerprintKind::Sha512);
     assert_eq!(sha256fp.kind, sshkeys::FingerprintKind::Sha256);
-    assert_eq!(sha256fp.hash, "elYqUIgEUqMyc8AdNNk+IeI+2l1vWEh4K4n03hqhoD8");
+    assert_eq!(
+        sha256fp.hash(),
+        "elYqUIgEUqMyc8AdNNk+IeI+2l1vWEh4K4n03hqhoD8"
+    );
     assert_eq!(sha384fp.kind, sshkeys::FingerprintKind::Sha384);
     assert_eq!(
-        sha384fp.hash,
+        sha384fp.hash(),
         "XPQbeB2kZcG3AUIjIq2wtUDMQYS/Iy0G6trb4XH97zzi4MK+YUqEdx7BAKkZYs0u"
     );
     assert_eq!(sha512fp.kind, sshkeys::FingerprintKind::Sha512);
     assert_eq!(
-        sha512fp.hash,
+        sha512fp.hash(),
         "nIa7CBs7SST41mSeHA7/69y7yy9y3Ec7W6JQKWJsgsBNbY3hq8WiPaa00z5q0AEgC+TO4MK56MoYY2PsE997zw"
     );
 
     assert_eq!(cert.comment, Some("me@home".to_string()));
 }
+
+#[test]
+fn test_cert_from_bytes() {
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    let data = contents.split_whitespace().nth(1).unwrap();
+    let decoded = base64::decode(data).unwrap();
+
+    let cert = sshkeys::Certificate::from_bytes(&decoded).unwrap();
+    let expected = sshkeys::Certificate::from_string(&contents).unwrap();
+
+    assert_eq!(cert.key_type, expected.key_type);
+    assert_eq!(cert.nonce, expected.nonce);
+    assert_eq!(cert.key, expected.key);
+    assert_eq!(cert.serial, expected.serial);
+    assert_eq!(cert.cert_type, expected.cert_type);
+    assert_eq!(cert.key_id, expected.key_id);
+    assert_eq!(cert.valid_principals, expected.valid_principals);
+    assert_eq!(cert.valid_after, expected.valid_after);
+    assert_eq!(cert.valid_before, expected.valid_before);
+    assert_eq!(cert.critical_options, expected.critical_options);
+    assert_eq!(cert.extensions, expected.extensions);
+    assert_eq!(cert.signature_key, expected.signature_key);
+    assert_eq!(cert.signature, expected.signature);
+    assert_eq!(cert.comment, None);
+}
+
+#[test]
+#[should_panic(expected = "Not a certificate")]
+fn test_cert_from_bytes_not_cert() {
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519.pub").unwrap();
+    let data = contents.split_whitespace().nth(1).unwrap();
+    let decoded = base64::decode(data).unwrap();
+
+    match sshkeys::Certificate::from_bytes(&decoded) {
+        Ok(v) => panic!("Expected public key, got certificate {:?}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+fn test_pubkey_from_reader_io() {
+    let file = fs::File::open("tests/test-keys/id_ed25519.pub").unwrap();
+    let key = sshkeys::PublicKey::from_reader_io(file).unwrap();
+    let expected = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+
+    assert_eq!(key, expected);
+
+    let contents = format!(
+        "{}  \n\n",
+        fs::read_to_string("tests/test-keys/id_ed25519.pub").unwrap()
+    );
+    let key = sshkeys::PublicKey::from_reader_io(contents.as_bytes()).unwrap();
+
+    assert_eq!(key, expected);
+    assert_eq!(key.comment, Some("me@home".to_string()));
+
+    // Leading blank lines are skipped and only the first key line is parsed,
+    // which is what `PublicKey::from_stdin` relies on
+    let contents = format!(
+        "\n  \n{}{}",
+        fs::read_to_string("tests/test-keys/id_ed25519.pub").unwrap(),
+        fs::read_to_string("tests/test-keys/id_rsa_2048.pub").unwrap()
+    );
+    let key = sshkeys::PublicKey::from_reader_io(contents.as_bytes()).unwrap();
+
+    assert_eq!(key, expected);
+    assert_eq!(key.comment, Some("me@home".to_string()));
+}
+
+#[test]
+fn test_md5_fingerprint() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let md5fp = key.fingerprint_with(sshkeys::FingerprintKind::Md5);
+
+    assert_eq!(md5fp.kind, sshkeys::FingerprintKind::Md5);
+    assert_eq!(
+        md5fp.hash(),
+        "93:6c:9e:4c:74:9b:62:df:ee:45:eb:6c:cb:69:b6:31"
+    );
+    assert_eq!(
+        md5fp.to_string(),
+        "MD5:93:6c:9e:4c:74:9b:62:df:ee:45:eb:6c:cb:69:b6:31"
+    );
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let md5fp = key.fingerprint_with(sshkeys::FingerprintKind::Md5);
+
+    assert_eq!(
+        md5fp.hash(),
+        "5c:a9:76:4d:35:31:a3:32:31:e3:4b:4a:e6:1f:46:21"
+    );
+}
+
+#[test]
+fn test_randomart() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let expected = "+--[ED25519 256]--+
+|      .o+E.      |
+|     o   +  .. ..|
+|    . o   o..+.oo|
+|   o   o . o. X.o|
+|  . o   S .  = +.|
+|   + . = o  .  ..|
+|  . . = + o    .o|
+|     . o = . o++ |
+|        . . .+O+o|
++----[SHA256]-----+";
+    assert_eq!(key.randomart(sshkeys::FingerprintKind::Sha256), expected);
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let expected = "+---[RSA 2048]----+
+|        E =   *. |
+|         o * o + |
+|        o O o    |
+|       = * *     |
+|        S = .    |
+|       . + .     |
+|          .      |
+|                 |
+|                 |
++------[MD5]------+";
+    assert_eq!(key.randomart(sshkeys::FingerprintKind::Md5), expected);
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_521.pub").unwrap();
+    let expected = "+---[ECDSA 521]---+
+|++oBo+ oo        |
+|o.+o%o.+.        |
+| . ..**.o     o .|
+|     oo=..     B |
+|    .  +S .   =  |
+|   .  oo.o . . o |
+|    .  .o . . oE.|
+|     o .   . o  .|
+|      o     . .  |
++----[SHA256]-----+";
+    assert_eq!(key.randomart(sshkeys::FingerprintKind::Sha256), expected);
+}
+
+#[test]
+fn test_pubkey_from_string_with_remainder() {
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519.pub").unwrap();
+    let line = format!("{} my laptop\r\n", contents.trim_end());
+
+    let (key, rest) = sshkeys::PublicKey::from_string_with_remainder(&line).unwrap();
+    assert_eq!(rest, "me@home my laptop");
+    assert_eq!(key.comment, Some("me@home my laptop".to_string()));
+
+    let key = sshkeys::PublicKey::from_string(&line).unwrap();
+    assert_eq!(key.comment, Some("me@home my laptop".to_string()));
+
+    let line = contents
+        .split_whitespace()
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let (key, rest) = sshkeys::PublicKey::from_string_with_remainder(&line).unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(key.comment, None);
+}
+
+#[test]
+fn test_ed25519_sk_pubkey() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519_sk.pub").unwrap();
+
+    assert_eq!(key.key_type.name, "sk-ssh-ed25519@openssh.com");
+    assert_eq!(key.key_type.plain, "sk-ssh-ed25519@openssh.com");
+    assert_eq!(key.key_type.short_name, "ED25519-SK");
+    assert!(!key.key_type.is_cert);
+    assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::SkEd25519);
+
+    assert_eq!(key.bits(), 256);
+    assert_eq!(key.comment, Some("me@home".to_string()));
+
+    let sha256fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha256);
+    assert_eq!(
+        sha256fp.hash(),
+        "HJGqe96+xEybJzJFeXi9gUw91w6wypK02MipR1jRiJ0"
+    );
+
+    let sk = match key.kind {
+        sshkeys::PublicKeyKind::SkEd25519(ref k) => k,
+        _ => panic!("Expected ED25519 security key public key"),
+    };
+
+    assert_eq!(sk.key.len(), 32);
+    assert_eq!(sk.application, "ssh:");
+}
+
+#[test]
+fn test_ecdsa_sk_pubkey() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_sk.pub").unwrap();
+
+    assert_eq!(key.key_type.name, "sk-ecdsa-sha2-nistp256@openssh.com");
+    assert_eq!(key.key_type.plain, "sk-ecdsa-sha2-nistp256@openssh.com");
+    assert_eq!(key.key_type.short_name, "ECDSA-SK");
+    assert!(!key.key_type.is_cert);
+    assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::SkEcdsa);
+
+    assert_eq!(key.bits(), 256);
+    assert_eq!(key.comment, Some("me@home".to_string()));
+
+    let sha256fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha256);
+    assert_eq!(
+        sha256fp.hash(),
+        "Vm/JaweOeTiZQwhrXJ2earkvCxvBG8AmfjTDOAv8iiM"
+    );
+
+    let sk = match key.kind {
+        sshkeys::PublicKeyKind::SkEcdsa(ref k) => k,
+        _ => panic!("Expected ECDSA security key public key"),
+    };
+
+    assert_eq!(sk.curve.kind, sshkeys::CurveKind::Nistp256);
+    assert_eq!(sk.curve.identifier, "nistp256");
+    assert_eq!(sk.key.len(), 65);
+    assert_eq!(sk.application, "ssh:");
+}
+
+#[test]
+fn test_pubkey_to_key_string() {
+    for path in &[
+        "tests/test-keys/id_rsa_1024.pub",
+        "tests/test-keys/id_rsa_2048.pub",
+        "tests/test-keys/id_dsa_1024.pub",
+        "tests/test-keys/id_ecdsa_256.pub",
+        "tests/test-keys/id_ecdsa_384.pub",
+        "tests/test-keys/id_ecdsa_521.pub",
+        "tests/test-keys/id_ed25519.pub",
+    ] {
+        let contents = fs::read_to_string(path).unwrap();
+        let key = sshkeys::PublicKey::from_string(&contents).unwrap();
+
+        assert_eq!(key.to_key_string(), contents.trim_end());
+        assert_eq!(key.to_string(), contents.trim_end());
+
+        let mut buf = Vec::new();
+        key.write(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), contents);
+    }
+}
+
+#[test]
+fn test_pubkey_write_to_path() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let path = std::env::temp_dir().join(format!("sshkeys-write-{}.pub", std::process::id()));
+
+    key.write_to_path(&path).unwrap();
+    let contents = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        contents,
+        fs::read_to_string("tests/test-keys/id_ed25519.pub").unwrap()
+    );
+    assert!(contents.ends_with('\n'));
+}
+
+#[test]
+fn test_ed448_pubkey() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed448.pub").unwrap();
+
+    assert_eq!(key.key_type.name, "ssh-ed448");
+    assert_eq!(key.key_type.plain, "ssh-ed448");
+    assert_eq!(key.key_type.short_name, "ED448");
+    assert!(!key.key_type.is_cert);
+    assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Ed448);
+
+    assert_eq!(key.bits(), 448);
+    assert_eq!(key.comment, Some("me@home".to_string()));
+
+    let sha256fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha256);
+    assert_eq!(
+        sha256fp.hash(),
+        "8ZTeUluOVDxbIePNf4qhujjOWk+rI1BdmNosMisghd8"
+    );
+
+    let ed448 = match key.kind {
+        sshkeys::PublicKeyKind::Ed448(ref k) => k,
+        _ => panic!("Expected ED448 public key"),
+    };
+
+    // Key size should be 57 bytes, RFC 8032, section 5.2.5
+    assert_eq!(ed448.key.len(), 57);
+
+    let contents = fs::read_to_string("tests/test-keys/id_ed448.pub").unwrap();
+    assert_eq!(key.to_key_string(), contents.trim_end());
+}
+
+#[test]
+fn test_xmss_pubkey() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_xmss.pub").unwrap();
+
+    assert_eq!(key.key_type.name, "ssh-xmss@openssh.com");
+    assert_eq!(key.key_type.plain, "ssh-xmss@openssh.com");
+    assert_eq!(key.key_type.short_name, "XMSS");
+    assert!(!key.key_type.is_cert);
+    assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Xmss);
+
+    assert_eq!(key.bits(), 256);
+    assert_eq!(key.comment, Some("me@home".to_string()));
+
+    let sha256fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha256);
+    assert_eq!(
+        sha256fp.hash(),
+        "+M0dXfUE0eE2jOaLI5VXnFpyyW54lqtGKMNFtFfu7as"
+    );
+
+    let xmss = match key.kind {
+        sshkeys::PublicKeyKind::Xmss(ref k) => k,
+        _ => panic!("Expected XMSS public key"),
+    };
+
+    assert_eq!(xmss.oid, "XMSS_SHA2_256_W16_H10");
+    assert_eq!(xmss.key.len(), 64);
+
+    assert_eq!(key.encoded_len(), key.encode().len());
+    assert_eq!(sshkeys::PublicKey::from_bytes(&key.encode()).unwrap(), key);
+
+    let contents = fs::read_to_string("tests/test-keys/id_xmss.pub").unwrap();
+    assert_eq!(key.to_key_string(), contents.trim_end());
+}
+
+#[test]
+fn test_pubkey_security_bits() {
+    let keys = [
+        ("id_rsa_1024.pub", 80),
+        ("id_rsa_1025.pub", 80),
+        ("id_rsa_2048.pub", 112),
+        ("id_dsa_1024.pub", 80),
+        ("id_ecdsa_256.pub", 128),
+        ("id_ecdsa_384.pub", 192),
+        ("id_ecdsa_521.pub", 256),
+        ("id_ecdsa_sk.pub", 128),
+        ("id_ed25519.pub", 128),
+        ("id_ed25519_sk.pub", 128),
+        ("id_ed448.pub", 224),
+        ("id_xmss.pub", 256),
+    ];
+
+    for &(name, bits) in keys.iter() {
+        let key = sshkeys::PublicKey::from_path(format!("tests/test-keys/{}", name)).unwrap();
+        assert_eq!(key.security_bits(), bits, "{}", name);
+    }
+
+    // The boundaries of the RSA modulus sizes
+    let sizes = [
+        (1023, 0),
+        (2047, 80),
+        (3072, 128),
+        (7679, 128),
+        (7680, 192),
+        (15360, 256),
+        (16384, 256),
+    ];
+
+    for &(size, bits) in sizes.iter() {
+        let mut n = vec![0xff; size / 8];
+        if size % 8 != 0 {
+            n.insert(0, (1 << (size % 8)) - 1);
+        }
+
+        let key = sshkeys::PublicKey::from_rsa(vec![1, 0, 1], n);
+        assert_eq!(key.bits(), size);
+        assert_eq!(key.security_bits(), bits, "{} bits", size);
+    }
+
+    // DSA keys are limited by the size of the subprime
+    let key = sshkeys::PublicKey::from_dsa(vec![0xff; 384], vec![0xff; 20], vec![2], vec![3]);
+    assert_eq!(key.security_bits(), 80);
+}
+
+#[test]
+fn test_pubkey_peek_key_type() {
+    let names = [
+        "id_rsa_2048.pub",
+        "id_dsa_1024.pub",
+        "id_ecdsa_256.pub",
+        "id_ecdsa_521.pub",
+        "id_ecdsa_sk.pub",
+        "id_ed25519.pub",
+        "id_ed25519_sk.pub",
+        "id_ed448.pub",
+        "id_xmss.pub",
+        "id_ed25519-cert.pub",
+    ];
+
+    for name in names.iter() {
+        let contents = fs::read_to_string(format!("tests/test-keys/{}", name)).unwrap();
+        let expected = sshkeys::KeyType::from_name(contents.split_whitespace().next().unwrap());
+
+        let kt = sshkeys::PublicKey::peek_key_type(&contents).unwrap();
+        assert_eq!(kt, expected.unwrap(), "{}", name);
+    }
+
+    // Only the key type is decoded, the rest of the key data is not validated
+    let kt = sshkeys::PublicKey::peek_key_type("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAA!!!!").unwrap();
+    assert_eq!(kt.kind, sshkeys::KeyTypeKind::Ed25519);
+}
+
+#[test]
+fn test_pubkey_peek_key_type_mismatch() {
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519.pub").unwrap();
+    let contents = contents.replacen("ssh-ed25519", "ssh-ed448", 1);
+
+    match sshkeys::PublicKey::peek_key_type(&contents) {
+        Ok(v) => panic!("Expected key type mismatch, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Key type mismatch"),
+    }
+
+    match sshkeys::PublicKey::peek_key_type("# comment") {
+        Ok(v) => panic!("Expected empty input, got {:?}", v),
+        Err(e) => assert!(e.is_empty_input()),
+    }
+
+    // The key data ends before the end of the key type
+    match sshkeys::PublicKey::peek_key_type("ssh-ed25519 AAAAC3NzaC1l") {
+        Ok(v) => panic!("Expected unexpected EOF, got {:?}", v),
+        Err(e) => assert!(e.to_string().starts_with("Unexpected EOF"), "{}", e),
+    }
+}
+
+#[test]
+fn test_pubkey_curve() {
+    let keys = [
+        ("id_ecdsa_256.pub", Some(sshkeys::CurveKind::Nistp256)),
+        ("id_ecdsa_384.pub", Some(sshkeys::CurveKind::Nistp384)),
+        ("id_ecdsa_521.pub", Some(sshkeys::CurveKind::Nistp521)),
+        ("id_ecdsa_sk.pub", Some(sshkeys::CurveKind::Nistp256)),
+        ("id_rsa_2048.pub", None),
+        ("id_dsa_1024.pub", None),
+        ("id_ed25519.pub", None),
+    ];
+
+    for &(name, ref curve) in keys.iter() {
+        let key = sshkeys::PublicKey::from_path(format!("tests/test-keys/{}", name)).unwrap();
+        assert_eq!(&key.curve(), curve, "{}", name);
+    }
+}
+
+#[test]
+fn test_pubkey_is_post_quantum() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_xmss.pub").unwrap();
+    assert!(key.is_post_quantum());
+
+    for path in &[
+        "tests/test-keys/id_rsa_2048.pub",
+        "tests/test-keys/id_dsa_1024.pub",
+        "tests/test-keys/id_ecdsa_256.pub",
+        "tests/test-keys/id_ed25519.pub",
+        "tests/test-keys/id_ed448.pub",
+    ] {
+        let key = sshkeys::PublicKey::from_path(path).unwrap();
+        assert!(!key.is_post_quantum(), "{} is not post-quantum", path);
+    }
+}
+
+#[test]
+fn test_rsa_pubkey_validate() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_1024.pub").unwrap();
+    let rsa = match key.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => k,
+        _ => panic!("Expected RSA public key"),
+    };
+
+    assert!(rsa.validate().is_ok());
+    assert!(rsa.validate_with_min_bits(1024).is_ok());
+    match rsa.validate_with_min_bits(2048) {
+        Ok(_) => panic!("Expected weak key"),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Weak key: RSA modulus of 1024 bits is smaller than 2048 bits"
+        ),
+    }
+
+    // Leading zero bytes do not count towards the modulus size
+    let mut n = vec![0, 0];
+    n.extend_from_slice(&rsa.n);
+    let padded = sshkeys::RsaPublicKey {
+        e: rsa.e.clone(),
+        n,
+    };
+    assert!(padded.validate_with_min_bits(1024).is_ok());
+    assert!(padded.validate_with_min_bits(1025).is_err());
+
+    let even = sshkeys::RsaPublicKey {
+        e: vec![1, 0, 0],
+        n: rsa.n.clone(),
+    };
+    match even.validate() {
+        Ok(_) => panic!("Expected weak key"),
+        Err(e) => assert_eq!(e.to_string(), "Weak key: RSA exponent is even"),
+    }
+
+    let one = sshkeys::RsaPublicKey {
+        e: vec![0, 1],
+        n: rsa.n.clone(),
+    };
+    match one.validate() {
+        Ok(_) => panic!("Expected weak key"),
+        Err(e) => assert_eq!(e.to_string(), "Weak key: RSA exponent is one"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Trailing data after key")]
+fn test_pubkey_trailing_data() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let mut data = key.encode();
+    data.extend_from_slice(&[0, 0, 0, 0]);
+
+    match sshkeys::PublicKey::from_bytes(&data) {
+        Ok(v) => panic!("Expected trailing data, got {:?}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Trailing data after key")]
+fn test_pubkey_from_string_trailing_data() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let mut data = key.encode();
+    data.push(0);
+    let line = format!("ssh-ed25519 {}", base64::encode(&data));
+
+    match sshkeys::PublicKey::from_string(&line) {
+        Ok(v) => panic!("Expected trailing data, got {:?}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+fn test_pubkey_matches() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let mut other = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    other.comment = Some("someone@else".to_string());
+
+    assert!(key.matches(&other));
+    assert_eq!(key, other);
+
+    let rsa = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    assert!(!key.matches(&rsa));
+}
+
+#[test]
+fn test_rsa_sha2_key_types() {
+    // The RSA signature algorithms with SHA-2 are no key types of their own
+    for name in &["rsa-sha2-256", "rsa-sha2-512"] {
+        match sshkeys::KeyType::from_name(name) {
+            Ok(v) => panic!("Expected unknown key type, got {:?}", v),
+            Err(e) => assert_eq!(e.to_string(), format!("Unknown key type {}", name)),
+        }
+    }
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("rsa-sha2-512");
+    let mut blob = writer.into_bytes();
+    blob.extend_from_slice(&key.encode()[4 + "ssh-rsa".len()..]);
+
+    match sshkeys::PublicKey::from_bytes(&blob) {
+        Ok(v) => panic!("Expected unknown key type, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Unknown key type rsa-sha2-512"),
+    }
+}
+
+#[test]
+fn test_fingerprint_raw() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+
+    let sha256fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha256);
+    assert_eq!(sha256fp.raw().len(), 32);
+    assert_eq!(
+        base64::encode(sha256fp.raw()).trim_end_matches('='),
+        sha256fp.hash()
+    );
+
+    let sha512fp = key.fingerprint_with(sshkeys::FingerprintKind::Sha512);
+    assert_eq!(sha512fp.raw().len(), 64);
+
+    let md5fp = key.fingerprint_with(sshkeys::FingerprintKind::Md5);
+    assert_eq!(
+        md5fp.raw(),
+        &[
+            0x93, 0x6c, 0x9e, 0x4c, 0x74, 0x9b, 0x62, 0xdf, 0xee, 0x45, 0xeb, 0x6c, 0xcb, 0x69,
+            0xb6, 0x31
+        ]
+    );
+}
+
+#[test]
+fn test_reader_unexpected_eof() {
+    let data = vec![0, 0, 0, 13, 97, 32, 116];
+    let mut reader = sshkeys::Reader::new(&data);
+
+    match reader.read_string() {
+        Ok(v) => panic!("Expected unexpected EOF, got {:?}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Unexpected EOF reached while reading 13 bytes at offset 4"
+        ),
+    }
+
+    let data = vec![0, 0, 0, 1, 42, 0, 0];
+    let mut reader = sshkeys::Reader::new(&data);
+    reader.read_bytes().unwrap();
+
+    match reader.read_u32() {
+        Ok(v) => panic!("Expected unexpected EOF, got {:?}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Unexpected EOF reached while reading 4 bytes at offset 5"
+        ),
+    }
+    assert_eq!(reader.position(), 5);
+    assert_eq!(reader.read_u32().unwrap_err().eof_offset(), Some((5, 4)));
+
+    // Truncated key blobs report the offset within the blob
+    let blob = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub")
+        .unwrap()
+        .encode();
+    let e = sshkeys::PublicKey::from_bytes(&blob[..blob.len() - 1]).unwrap_err();
+    assert_eq!(e.eof_offset(), Some((19, 32)));
+    assert!(!e.is_empty_input());
+}
+
+#[test]
+fn test_writer_reader_u64_round_trip() {
+    let mut writer = sshkeys::Writer::new();
+    writer.write_u64(0x0102_0304_0506_0708);
+    writer.write_u32(42);
+    writer.write_u64(u64::MAX);
+    let bytes = writer.into_bytes();
+
+    let mut reader = sshkeys::Reader::new(&bytes);
+    assert_eq!(reader.read_u64().unwrap(), 0x0102_0304_0506_0708);
+    assert_eq!(reader.read_u32().unwrap(), 42);
+    assert_eq!(reader.read_u64().unwrap(), u64::MAX);
+    assert_eq!(reader.remaining(), 0);
+
+    match reader.read_u64() {
+        Ok(v) => panic!("Expected unexpected EOF, got {}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Unexpected EOF reached while reading 8 bytes at offset 20"
+        ),
+    }
+}
+
+#[test]
+fn test_public_key_eq_and_hash_ignore_comment() {
+    use std::collections::HashSet;
+
+    let a = sshkeys::PublicKey::from_string(
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home",
+    )
+    .unwrap();
+    let b = sshkeys::PublicKey::from_string(
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@work",
+    )
+    .unwrap();
+    let c = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    let set: HashSet<sshkeys::PublicKey> = vec![a, b, c].into_iter().collect();
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_pubkey_constructors() {
+    let rsa = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let (e, n) = match rsa.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => (k.e.clone(), k.n.clone()),
+        _ => panic!("Expected RSA public key"),
+    };
+
+    // Leading zero bytes of the mpint encoding are accepted
+    let mut padded_n = vec![0];
+    padded_n.extend_from_slice(&n);
+    let key = sshkeys::PublicKey::from_rsa(e, padded_n);
+    assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Rsa);
+    assert_eq!(key.comment, None);
+    assert_eq!(key.bits(), 2048);
+    assert_eq!(key.fingerprint().hash(), rsa.fingerprint().hash());
+    assert_eq!(key, rsa);
+
+    let ed25519 = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let bytes = match ed25519.kind {
+        sshkeys::PublicKeyKind::Ed25519(ref k) => k.key.clone(),
+        _ => panic!("Expected ED25519 public key"),
+    };
+    assert_eq!(sshkeys::PublicKey::from_ed25519(bytes), ed25519);
+
+    let ecdsa = sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_521.pub").unwrap();
+    let bytes = match ecdsa.kind {
+        sshkeys::PublicKeyKind::Ecdsa(ref k) => k.key.clone(),
+        _ => panic!("Expected ECDSA public key"),
+    };
+    let curve = sshkeys::Curve::from_identifier("nistp521").unwrap();
+    let key = sshkeys::PublicKey::from_ecdsa(curve, bytes);
+    assert_eq!(key.key_type.name, "ecdsa-sha2-nistp521");
+    assert_eq!(key, ecdsa);
+}
+
+#[test]
+fn test_ecdsa_curve_mismatch() {
+    let cases = [
+        ("ecdsa-sha2-nistp256", "nistp384"),
+        ("ecdsa-sha2-nistp384", "nistp521"),
+        ("ecdsa-sha2-nistp521", "nistp256"),
+        ("sk-ecdsa-sha2-nistp256@openssh.com", "nistp384"),
+    ];
+
+    for &(kt_name, identifier) in cases.iter() {
+        let mut writer = sshkeys::Writer::new();
+        writer.write_string(kt_name);
+        writer.write_string(identifier);
+        writer.write_bytes(&[4; 65]);
+        writer.write_string("ssh:");
+        let data = writer.into_bytes();
+
+        match sshkeys::PublicKey::from_bytes(&data) {
+            Ok(v) => panic!("Expected curve mismatch, got {:?}", v),
+            Err(e) => assert_eq!(e.to_string(), "Curve does not match key type"),
+        }
+    }
+}
+
+#[test]
+fn test_ecdsa_invalid_point() {
+    let mut compressed = vec![2];
+    compressed.extend_from_slice(&[0xab; 32]);
+    let mut wrong_prefix = vec![3];
+    wrong_prefix.extend_from_slice(&[0xab; 132]);
+
+    let cases = [
+        ("ecdsa-sha2-nistp256", "nistp256", compressed),
+        ("ecdsa-sha2-nistp384", "nistp384", vec![4; 65]),
+        ("ecdsa-sha2-nistp521", "nistp521", vec![4; 132]),
+        ("ecdsa-sha2-nistp521", "nistp521", wrong_prefix),
+        (
+            "sk-ecdsa-sha2-nistp256@openssh.com",
+            "nistp256",
+            vec![4; 64],
+        ),
+    ];
+
+    for &(kt_name, identifier, ref point) in cases.iter() {
+        let mut writer = sshkeys::Writer::new();
+        writer.write_string(kt_name);
+        writer.write_string(identifier);
+        writer.write_bytes(point);
+        writer.write_string("ssh:");
+        let data = writer.into_bytes();
+
+        match sshkeys::PublicKey::from_bytes(&data) {
+            Ok(v) => panic!("Expected invalid point, got {:?}", v),
+            Err(e) => assert_eq!(e.to_string(), "Invalid elliptic curve point"),
+        }
+    }
+}
+
+#[test]
+fn test_fingerprint_from_str() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+
+    let kinds = vec![
+        sshkeys::FingerprintKind::Sha256,
+        sshkeys::FingerprintKind::Sha384,
+        sshkeys::FingerprintKind::Sha512,
+        sshkeys::FingerprintKind::Md5,
+    ];
+
+    for kind in kinds {
+        let fp = key.fingerprint_with(kind);
+        let parsed: sshkeys::Fingerprint = fp.to_string().parse().unwrap();
+        assert_eq!(parsed.kind, fp.kind);
+        assert_eq!(parsed.hash(), fp.hash());
+        assert_eq!(parsed.raw(), fp.raw());
+        assert_eq!(parsed.to_string(), fp.to_string());
+    }
+
+    let invalid = vec![
+        "",
+        "SHA256",
+        "SHA1:EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6AkDe4",
+        "SHA256:EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6Ak",
+        "SHA512:EweZDmulyhRes16ZGCqb7EZTG8VN32VqYCx4D6AkDe4",
+        "MD5:93:6c:9e:4c:74:9b:62:df:ee:45:eb:6c:cb:69:b6",
+        "MD5:93:6c:9e:4c:74:9b:62:df:ee:45:eb:6c:cb:69:b6:zz",
+        "MD5:936c9e4c749b62dfee45eb6ccb69b631",
+    ];
+
+    for s in invalid {
+        assert!(s.parse::<sshkeys::Fingerprint>().is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn test_rsa_pubkey_rfc4716() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048_rfc4716.pub").unwrap();
+    let openssh = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+
+    assert_eq!(key.key_type.name, "ssh-rsa");
+    assert_eq!(key.bits(), 2048);
+    assert_eq!(
+        key.comment,
+        Some("2048-bit RSA, converted by me@home from OpenSSH, with a comment spanning more than a single line".to_string())
+    );
+    assert_eq!(key.fingerprint().hash(), openssh.fingerprint().hash());
+    assert_eq!(key, openssh);
+}
+
+#[test]
+fn test_pubkey_rfc4716_without_comment() {
+    let data = "\r\n---- BEGIN SSH2 PUBLIC KEY ----\r\nAAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd\r\n---- END SSH2 PUBLIC KEY ----\r\n";
+    let key = sshkeys::PublicKey::from_string(data).unwrap();
+
+    assert_eq!(key.key_type.name, "ssh-ed25519");
+    assert_eq!(key.comment, None);
+    assert_eq!(
+        key.fingerprint().hash(),
+        "ciQkdxjFUhk2E2vRkWJD9kB8pi+EneOkaCJJHNWzPC4"
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid format")]
+fn test_pubkey_rfc4716_missing_end() {
+    let data = "---- BEGIN SSH2 PUBLIC KEY ----\nAAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd\n";
+
+    match sshkeys::PublicKey::from_string(data) {
+        Ok(v) => panic!("Expected invalid format, got {:?}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+fn test_pubkey_to_rfc4716() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048_rfc4716.pub").unwrap();
+    let expected = fs::read_to_string("tests/test-keys/id_rsa_2048_rfc4716.pub").unwrap();
+
+    // The fixture contains an additional header, which is not preserved
+    let expected: Vec<&str> = expected
+        .lines()
+        .filter(|l| !l.starts_with("x-command"))
+        .collect();
+    let exported = key.to_rfc4716();
+    assert_eq!(exported.lines().collect::<Vec<&str>>(), expected);
+    assert!(exported.lines().all(|l| l.len() <= 72));
+
+    let parsed = sshkeys::PublicKey::from_string(&exported).unwrap();
+    assert_eq!(parsed, key);
+    assert_eq!(parsed.comment, key.comment);
+}
+
+#[test]
+fn test_pubkey_to_rfc4716_round_trip() {
+    let comments = vec![
+        None,
+        Some("me@home".to_string()),
+        Some(" leading and trailing spaces, split right at a space character ... ".to_string()),
+        Some("ünïcödé ".repeat(20)),
+    ];
+
+    for comment in comments {
+        let mut key = sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_521.pub").unwrap();
+        key.comment = comment;
+
+        let exported = key.to_rfc4716();
+        assert!(exported.lines().all(|l| l.len() <= 72));
+
+        let parsed = sshkeys::PublicKey::from_rfc4716(&exported).unwrap();
+        assert_eq!(parsed, key);
+        assert_eq!(
+            parsed.comment,
+            key.comment.as_ref().map(|c| c.trim().to_string())
+        );
+    }
+}
+
+#[test]
+fn test_pubkey_iter_from_reader() {
+    let mut data = String::from("# public keys\n\n");
+    for name in &["id_rsa_2048", "id_dsa_1024", "id_ecdsa_256", "id_ed25519"] {
+        data.push_str(&fs::read_to_string(format!("tests/test-keys/{}.pub", name)).unwrap());
+        data.push_str("   \n");
+    }
+    data.push_str("ssh-foo AAAA\n");
+    data.push_str("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home\n");
+
+    let mut iter = sshkeys::PublicKey::iter_from_reader(data.as_bytes());
+    let kinds: Vec<sshkeys::KeyTypeKind> = iter
+        .by_ref()
+        .take(4)
+        .map(|k| k.unwrap().key_type.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            sshkeys::KeyTypeKind::Rsa,
+            sshkeys::KeyTypeKind::Dsa,
+            sshkeys::KeyTypeKind::Ecdsa,
+            sshkeys::KeyTypeKind::Ed25519,
+        ]
+    );
+
+    match iter.next() {
+        Some(Err(e)) => assert_eq!(e.to_string(), "Unknown key type ssh-foo"),
+        _ => panic!("Expected unknown key type"),
+    }
+
+    let key = iter.next().unwrap().unwrap();
+    assert_eq!(key.comment, Some("me@home".to_string()));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_pubkey_iter_from_reader_multi_line() {
+    let read = |name: &str| fs::read_to_string(format!("tests/test-keys/{}", name)).unwrap();
+    let public_ppk = "PuTTY-User-Key-File-2: ssh-ed25519\nEncryption: none\nComment: me@home\nPublic-Lines: 2\nAAAAC3NzaC1lZDI1NTE5AAAAIMIVp6q5co/r5GwY0dH+NYQbfKicapeF3gXEU3dz\naAvD\n";
+
+    let mut data = read("id_ed25519.pub");
+    data.push_str(&read("id_rsa_2048_rfc4716.pub"));
+    data.push_str(&read("id_ecdsa_256.pub"));
+    data.push_str(&read("id_ed25519_v2.ppk"));
+    data.push_str(&read("id_dsa_1024.pub"));
+    // A PuTTY key without the private lines ends with the first line which is not a header
+    data.push_str(public_ppk);
+    data.push_str(&read("id_rsa_2048.pub"));
+
+    let keys = sshkeys::PublicKey::iter_from_reader(data.as_bytes())
+        .collect::<sshkeys::Result<Vec<_>>>()
+        .unwrap();
+
+    let expected = vec![
+        sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap(),
+        sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048_rfc4716.pub").unwrap(),
+        sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_256.pub").unwrap(),
+        sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519_v2.ppk").unwrap(),
+        sshkeys::PublicKey::from_path("tests/test-keys/id_dsa_1024.pub").unwrap(),
+        sshkeys::PublicKey::from_ppk(public_ppk).unwrap(),
+        sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap(),
+    ];
+
+    assert_eq!(keys, expected);
+    for (key, expected) in keys.iter().zip(expected.iter()) {
+        assert_eq!(key.comment, expected.comment);
+    }
+}
+
+#[test]
+fn test_pubkey_iter_from_reader_unterminated_rfc4716() {
+    let mut data = fs::read_to_string("tests/test-keys/id_ed25519.pub").unwrap();
+    let rfc4716 = fs::read_to_string("tests/test-keys/id_rsa_2048_rfc4716.pub").unwrap();
+    data.push_str(
+        rfc4716
+            .trim_end()
+            .trim_end_matches("---- END SSH2 PUBLIC KEY ----"),
+    );
+
+    let mut iter = sshkeys::PublicKey::iter_from_reader(data.as_bytes());
+    assert!(iter.next().unwrap().is_ok());
+
+    match iter.next() {
+        Some(Err(e)) => assert_eq!(e.to_string(), "Invalid format"),
+        _ => panic!("Expected invalid format"),
+    }
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_fingerprint_constant_time_eq() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let other = sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_256.pub").unwrap();
+
+    let fp = key.fingerprint();
+    assert!(fp.constant_time_eq(&key.fingerprint()));
+    assert!(!fp.constant_time_eq(&other.fingerprint()));
+
+    let pinned: sshkeys::Fingerprint = fp.to_string().parse().unwrap();
+    assert!(fp.constant_time_eq(&pinned));
+
+    // The same digest encoded with a different kind is not equal
+    let md5fp = key.fingerprint_with(sshkeys::FingerprintKind::Md5);
+    assert!(!fp.constant_time_eq(&md5fp));
+    assert!(!md5fp.constant_time_eq(&fp));
+}
+
+#[test]
+fn test_fingerprint_constant_time_eq_base64_config() {
+    let key = sshkeys::PublicKey::from_string(
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home",
+    )
+    .unwrap();
+    let data = key.encode();
+
+    // The same digest encoded with different base64 configs is equal
+    let fp = sshkeys::Fingerprint::compute(sshkeys::FingerprintKind::Sha256, &data);
+    assert_eq!(fp.hash(), "ciQkdxjFUhk2E2vRkWJD9kB8pi+EneOkaCJJHNWzPC4");
+    for config in &[base64::STANDARD, base64::URL_SAFE, base64::URL_SAFE_NO_PAD] {
+        let other = sshkeys::Fingerprint::compute_with_config(
+            sshkeys::FingerprintKind::Sha256,
+            &data,
+            *config,
+        );
+        assert_ne!(other.hash(), fp.hash());
+        assert!(fp.constant_time_eq(&other));
+        assert!(other.constant_time_eq(&fp));
+    }
+}
+
+#[test]
+fn test_pubkey_clone() {
+    let names = vec![
+        "id_rsa_2048.pub",
+        "id_dsa_1024.pub",
+        "id_ecdsa_384.pub",
+        "id_ed25519.pub",
+        "id_ed448.pub",
+        "id_ed25519_sk.pub",
+        "id_ecdsa_sk.pub",
+    ];
+
+    for name in names {
+        let key = sshkeys::PublicKey::from_path(format!("tests/test-keys/{}", name)).unwrap();
+        let cloned = key.clone();
+
+        assert_eq!(cloned.encode(), key.encode());
+        assert_eq!(cloned.kind, key.kind);
+        assert_eq!(cloned.key_type, key.key_type);
+        assert_eq!(cloned.comment, key.comment);
+    }
+}
+
+#[test]
+fn test_ecdsa_secp256k1_pubkey() {
+    let mut point = vec![4];
+    point.extend_from_slice(&[0x5c; 64]);
+
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ecdsa-sha2-secp256k1");
+    writer.write_string("secp256k1");
+    writer.write_bytes(&point);
+    let data = writer.into_bytes();
+
+    let key = sshkeys::PublicKey::from_bytes(&data).unwrap();
+    assert_eq!(key.key_type.name, "ecdsa-sha2-secp256k1");
+    assert_eq!(key.key_type.short_name, "ECDSA");
+    assert_eq!(key.key_type.kind, sshkeys::KeyTypeKind::Ecdsa);
+    assert_eq!(key.bits(), 256);
+    assert_eq!(key.encode(), data);
+
+    match key.kind {
+        sshkeys::PublicKeyKind::Ecdsa(ref k) => {
+            assert_eq!(k.curve.kind, sshkeys::CurveKind::Secp256k1);
+            assert_eq!(k.curve.identifier, "secp256k1");
+            assert_eq!(k.key, point);
+        }
+        _ => panic!("Expected ECDSA public key"),
+    }
+
+    let parsed = sshkeys::PublicKey::from_string(&key.to_key_string()).unwrap();
+    assert_eq!(parsed, key);
+
+    let curve = sshkeys::Curve::from_identifier("secp256k1").unwrap();
+    assert_eq!(sshkeys::PublicKey::from_ecdsa(curve, point), key);
+}
+
+#[test]
+fn test_pubkey_algorithm() {
+    let cases = vec![
+        ("id_rsa_2048.pub", "ssh-rsa"),
+        ("id_dsa_1024.pub", "ssh-dss"),
+        ("id_ecdsa_256.pub", "ecdsa-sha2-nistp256"),
+        ("id_ecdsa_521.pub", "ecdsa-sha2-nistp521"),
+        ("id_ed25519.pub", "ssh-ed25519"),
+        ("id_ed25519_sk.pub", "sk-ssh-ed25519@openssh.com"),
+    ];
+
+    for (name, algorithm) in cases {
+        let key = sshkeys::PublicKey::from_path(format!("tests/test-keys/{}", name)).unwrap();
+        assert_eq!(key.algorithm(), algorithm);
+    }
+
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ecdsa_521-cert.pub").unwrap();
+    assert_eq!(cert.key.algorithm(), "ecdsa-sha2-nistp521");
+}
+
+#[test]
+fn test_is_certificate() {
+    let names = vec![
+        "ssh-rsa",
+        "ssh-dss",
+        "ecdsa-sha2-nistp256",
+        "ssh-ed25519",
+        "ssh-ed448",
+        "sk-ssh-ed25519@openssh.com",
+        "sk-ecdsa-sha2-nistp256@openssh.com",
+        "ssh-rsa-cert-v01@openssh.com",
+        "ssh-dss-cert-v01@openssh.com",
+        "ecdsa-sha2-nistp384-cert-v01@openssh.com",
+        "ssh-ed25519-cert-v01@openssh.com",
+    ];
+
+    for name in names {
+        let kt = sshkeys::KeyType::from_name(name).unwrap();
+        assert_eq!(kt.is_cert(), kt.is_cert, "{}", name);
+        assert_eq!(kt.is_cert(), name.ends_with("-cert-v01@openssh.com"));
+    }
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    assert!(!key.is_certificate());
+
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    assert!(cert.key.is_certificate());
+    assert!(!cert.signature_key.is_certificate());
+}
+
+#[test]
+fn test_reader_huge_length_prefix() {
+    let data = vec![0xff, 0xff, 0xff, 0xff, 1, 2, 3];
+    let mut reader = sshkeys::Reader::new(&data);
+
+    match reader.read_bytes() {
+        Ok(v) => panic!("Expected unexpected EOF, got {:?}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Unexpected EOF reached while reading 4294967295 bytes at offset 4"
+        ),
+    }
+    assert_eq!(reader.position(), 0);
+
+    // A huge length prefix for the key data of an otherwise valid key
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ssh-ed25519");
+    let mut data = writer.into_bytes();
+    data.extend_from_slice(&[0xff, 0xff, 0xff, 0xfc, 42]);
+
+    assert!(sshkeys::PublicKey::from_bytes(&data).is_err());
+    for len in 0..data.len() {
+        assert!(sshkeys::PublicKey::from_bytes(&data[..len]).is_err());
+    }
+}
+
+#[test]
+fn test_from_bytes_never_panics() {
+    // Inputs which used to panic instead of returning an error
+    let corpus: Vec<Vec<u8>> = vec![
+        // RSA public key with an empty exponent
+        vec![0, 0, 0, 7, 115, 115, 104, 45, 114, 115, 97, 0, 0, 0, 0],
+        // RSA public key with an empty modulus
+        vec![
+            0, 0, 0, 7, 115, 115, 104, 45, 114, 115, 97, 0, 0, 0, 1, 3, 0, 0, 0, 0,
+        ],
+        // ECDSA public key with an empty point
+        vec![
+            0, 0, 0, 19, 101, 99, 100, 115, 97, 45, 115, 104, 97, 50, 45, 110, 105, 115, 116, 112,
+            50, 53, 54, 0, 0, 0, 8, 110, 105, 115, 116, 112, 50, 53, 54, 0, 0, 0, 0,
+        ],
+        // Truncated length prefix
+        vec![0, 0, 0],
+        vec![0xff, 0xff, 0xff, 0xff],
+    ];
+
+    for data in &corpus {
+        let _ = sshkeys::PublicKey::from_bytes(data);
+        let _ = sshkeys::Certificate::from_bytes(data);
+    }
+
+    // Truncate and mutate all sample keys and certificates
+    let names = vec![
+        "id_rsa_2048.pub",
+        "id_dsa_1024.pub",
+        "id_ecdsa_521.pub",
+        "id_ed25519.pub",
+        "id_ed25519_sk.pub",
+        "id_ecdsa_sk.pub",
+        "id_rsa_2048-cert.pub",
+        "id_ecdsa_521-cert.pub",
+        "id_ed25519-cert.pub",
+    ];
+
+    for name in names {
+        let contents = fs::read_to_string(format!("tests/test-keys/{}", name)).unwrap();
+        let data = base64::decode(contents.split_whitespace().nth(1).unwrap()).unwrap();
+
+        for len in 0..data.len() {
+            if name.ends_with("-cert.pub") {
+                assert!(sshkeys::Certificate::from_bytes(&data[..len]).is_err());
+            } else {
+                assert!(sshkeys::PublicKey::from_bytes(&data[..len]).is_err());
+            }
+        }
+
+        for i in 0..data.len() {
+            for b in &[0x00, 0x80, 0xff] {
+                let mut mutated = data.clone();
+                mutated[i] = *b;
+                let _ = sshkeys::PublicKey::from_bytes(&mutated);
+                let _ = sshkeys::Certificate::from_bytes(&mutated);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pubkey_encode_matches_original_blob() {
+    let names = vec![
+        "id_rsa_1024.pub",
+        "id_rsa_2048.pub",
+        "id_dsa_1024.pub",
+        "id_ecdsa_256.pub",
+        "id_ecdsa_384.pub",
+        "id_ecdsa_521.pub",
+        "id_ed25519.pub",
+    ];
+
+    for name in names {
+        let contents = fs::read_to_string(format!("tests/test-keys/{}", name)).unwrap();
+        let blob = base64::decode(contents.split_whitespace().nth(1).unwrap()).unwrap();
+        let key = sshkeys::PublicKey::from_string(&contents).unwrap();
+
+        assert_eq!(key.encode(), blob, "{}", name);
+    }
+
+    // The modulus of the 2048 bit RSA sample key has the high bit set,
+    // and is encoded with an additional leading zero byte.
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    match key.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => {
+            assert_eq!(k.n.len(), 256);
+            assert!(k.n[0] & 0x80 != 0);
+        }
+        _ => panic!("Expected RSA public key"),
+    }
+
+    // Redundant leading zero bytes are not written
+    let key = sshkeys::PublicKey::new(
+        sshkeys::KeyType::from_name("ssh-rsa").unwrap(),
+        sshkeys::PublicKeyKind::Rsa(sshkeys::RsaPublicKey {
+            e: vec![0, 0, 1, 0, 1],
+            n: vec![0, 0, 0, 0xc5, 0x1b],
+        }),
+        None,
+    );
+    let data = key.encode();
+    let mut reader = sshkeys::Reader::new(&data);
+    assert_eq!(reader.read_string().unwrap(), "ssh-rsa");
+    assert_eq!(reader.read_bytes().unwrap(), vec![1, 0, 1]);
+    assert_eq!(reader.read_bytes().unwrap(), vec![0, 0xc5, 0x1b]);
+}
+
+#[test]
+fn test_cert_signature_key() {
+    let cert =
+        sshkeys::Certificate::from_path("tests/test-keys/id_ed25519_ecdsa_ca-cert.pub").unwrap();
+
+    assert_eq!(cert.key_id, "ecdsa-signed");
+    assert_eq!(cert.signature_key.key_type.name, "ecdsa-sha2-nistp384");
+    assert_eq!(cert.signature_key.bits(), 384);
+    assert!(!cert.signature_key.is_certificate());
+    assert_eq!(
+        cert.signature_key.fingerprint().hash(),
+        "GWNZ/+mMkNGKigKk78KR8Ip1PUg2uqBYfp1G/fGHcCQ"
+    );
+
+    let rsa_cert = sshkeys::Certificate::from_path("tests/test-keys/id_rsa_2048-cert.pub").unwrap();
+    assert!(!rsa_cert.signature_key.matches(&cert.signature_key));
+}
+
+#[test]
+fn test_cert_signature_key_is_cert() {
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    let data = base64::decode(contents.split_whitespace().nth(1).unwrap()).unwrap();
+    let cert = sshkeys::Certificate::from_bytes(&data).unwrap();
+
+    // Replace the signature key at the end of the certificate with a certificate
+    let tail = 8 + cert.signature_key.encode().len() + cert.signature.len();
+    let mut encoded = data[..data.len() - tail].to_vec();
+    let mut writer = sshkeys::Writer::new();
+    writer.write_bytes(&data);
+    writer.write_bytes(&cert.signature);
+    encoded.extend_from_slice(&writer.into_bytes());
+
+    assert!(sshkeys::Certificate::from_bytes(&encoded).is_err());
+}
+
+#[test]
+fn test_cert_is_valid_at() {
+    let mut cert = sshkeys::Certificate::from_path("tests/test-keys/id_rsa_2048-cert.pub").unwrap();
+    assert_eq!(cert.valid_after, 1505374860);
+    assert_eq!(cert.valid_before, 1536824561);
+
+    assert!(!cert.is_valid_at(0));
+    assert!(!cert.is_valid_at(1505374859));
+    assert!(cert.is_valid_at(1505374860));
+    assert!(cert.is_valid_at(1536824560));
+    assert!(!cert.is_valid_at(1536824561));
+    assert!(!cert.is_valid_at(u64::MAX));
+    assert!(!cert.is_valid_now());
+
+    // Certificates which never expire
+    cert.valid_after = 0;
+    cert.valid_before = u64::MAX;
+    assert!(cert.is_valid_at(0));
+    assert!(cert.is_valid_at(u64::MAX));
+    assert!(cert.is_valid_now());
+
+    let cert =
+        sshkeys::Certificate::from_path("tests/test-keys/id_ed25519_ecdsa_ca-cert.pub").unwrap();
+    assert!(cert.is_valid_at(1600000000));
+    assert!(!cert.is_valid_at(1900000000));
+}
+
+#[test]
+fn test_cert_is_principal_allowed() {
+    let mut cert = sshkeys::Certificate::from_path("tests/test-keys/id_rsa_2048-cert.pub").unwrap();
+    assert!(cert.is_principal_allowed("root"));
+    assert!(!cert.is_principal_allowed("Root"));
+    assert!(!cert.is_principal_allowed("admin"));
+    assert!(!cert.is_principal_allowed(""));
+
+    // A certificate without principals is valid for any principal
+    cert.valid_principals.clear();
+    assert!(cert.is_principal_allowed("root"));
+    assert!(cert.is_principal_allowed("admin"));
+
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519_host-cert.pub").unwrap();
+    assert_eq!(cert.cert_type, sshkeys::CertType::Host);
+    assert!(cert.is_principal_allowed("host01.example.com"));
+    assert!(!cert.is_principal_allowed("host02.example.com"));
+}
+
+#[test]
+fn test_cert_critical_option_and_extension() {
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_rsa_2048-cert.pub").unwrap();
+
+    assert_eq!(
+        cert.critical_option("force-command").unwrap(),
+        Some("/usr/bin/true".to_string())
+    );
+    assert_eq!(
+        cert.critical_option("source-address").unwrap(),
+        Some("127.0.0.1".to_string())
+    );
+    assert_eq!(cert.critical_option("verify-required").unwrap(), None);
+
+    assert_eq!(cert.extension("permit-pty").unwrap(), Some("".to_string()));
+    assert_eq!(cert.extension("no-touch-required").unwrap(), None);
+
+    // Options are ordered by name
+    let names: Vec<&String> = cert.extensions.keys().collect();
+    assert_eq!(
+        names,
+        vec![
+            "permit-X11-forwarding",
+            "permit-agent-forwarding",
+            "permit-port-forwarding",
+            "permit-pty",
+            "permit-user-rc",
+        ]
+    );
+}
+
+// Encodes an ED25519 user certificate with the given critical options.
+fn encode_cert_with_options(options: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let key_data = match key.kind {
+        sshkeys::PublicKeyKind::Ed25519(ref k) => k.key.clone(),
+        _ => panic!("Expected ED25519 public key"),
+    };
+
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ssh-ed25519-cert-v01@openssh.com");
+    writer.write_bytes(&[0; 32]);
+    writer.write_bytes(&key_data);
+    writer.write_u64(1);
+    writer.write_u32(1);
+    writer.write_string("test");
+    writer.write_nested(|w| w.write_string("root"));
+    writer.write_u64(0);
+    writer.write_u64(u64::MAX);
+    writer.write_nested(|w| {
+        for &(name, ref data) in options {
+            w.write_string(name);
+            w.write_bytes(data);
+        }
+    });
+    writer.write_nested(|w| {
+        w.write_string("permit-pty");
+        w.write_bytes(&[]);
+    });
+    writer.write_bytes(&[]);
+    writer.write_bytes(&key.encode());
+    writer.write_nested(|w| {
+        w.write_string("ssh-ed25519");
+        w.write_bytes(&[0; 64]);
+    });
+
+    writer.into_bytes()
+}
+
+#[test]
+fn test_cert_options_order() {
+    let data = encode_cert_with_options(&[
+        ("force-command", option_value("/bin/ls")),
+        ("source-address", option_value("10.0.0.0/8")),
+    ]);
+    let cert = sshkeys::Certificate::from_bytes(&data).unwrap();
+    assert_eq!(
+        cert.critical_option("force-command").unwrap(),
+        Some("/bin/ls".to_string())
+    );
+    assert_eq!(
+        cert.critical_options["source-address"],
+        option_value("10.0.0.0/8")
+    );
+
+    let unordered = encode_cert_with_options(&[
+        ("source-address", option_value("10.0.0.0/8")),
+        ("force-command", option_value("/bin/ls")),
+    ]);
+    match sshkeys::Certificate::from_bytes(&unordered) {
+        Ok(v) => panic!("Expected invalid format, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Invalid format"),
+    }
+
+    let duplicate = encode_cert_with_options(&[
+        ("force-command", option_value("/bin/ls")),
+        ("force-command", option_value("/bin/sh")),
+    ]);
+    assert!(sshkeys::Certificate::from_bytes(&duplicate).is_err());
+
+    // The option data of string options must contain exactly one string
+    let mut invalid = option_value("/bin/ls");
+    invalid.push(0);
+    let data = encode_cert_with_options(&[("force-command", invalid)]);
+    let cert = sshkeys::Certificate::from_bytes(&data).unwrap();
+    assert!(cert.critical_option("force-command").is_err());
+}
+
+#[test]
+fn test_cert_builder_matches_openssh() {
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    let data = base64::decode(contents.split_whitespace().nth(1).unwrap()).unwrap();
+    let cert = sshkeys::Certificate::from_bytes(&data).unwrap();
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let mut builder =
+        sshkeys::CertificateBuilder::new(key, sshkeys::CertType::User, cert.signature_key.clone())
+            .nonce(cert.nonce.clone())
+            .serial(cert.serial)
+            .key_id(&cert.key_id)
+            .valid_after(cert.valid_after)
+            .valid_before(cert.valid_before)
+            .critical_option("force-command", "/usr/bin/true");
+
+    for p in &cert.valid_principals {
+        builder = builder.principal(p);
+    }
+
+    // Extensions are added in reverse order, the builder must sort them
+    for name in cert.extensions.keys().rev() {
+        builder = builder.extension(name);
+    }
+
+    let tbs = builder.build().unwrap();
+    assert_eq!(tbs, &data[..data.len() - 4 - cert.signature.len()]);
+}
+
+#[test]
+fn test_cert_builder_round_trip() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let ca = sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_256.pub").unwrap();
+
+    let tbs = sshkeys::CertificateBuilder::new(key.clone(), sshkeys::CertType::Host, ca.clone())
+        .serial(42)
+        .key_id("host.example.com")
+        .principal("host.example.com")
+        .principal("10.0.0.1")
+        .valid_after(1577836800)
+        .valid_before(1893456000)
+        .build()
+        .unwrap();
+
+    let mut writer = sshkeys::Writer::new();
+    writer.write_nested(|w| {
+        w.write_string("ecdsa-sha2-nistp256");
+        w.write_bytes(&[0; 8]);
+    });
+    let mut data = tbs;
+    data.extend_from_slice(&writer.into_bytes());
+
+    let cert = sshkeys::Certificate::from_bytes(&data).unwrap();
+    assert_eq!(cert.key_type.name, "ssh-ed25519-cert-v01@openssh.com");
+    assert_eq!(cert.nonce.len(), 32);
+    assert_eq!(cert.key, key);
+    assert_eq!(cert.serial, 42);
+    assert_eq!(cert.cert_type, sshkeys::CertType::Host);
+    assert_eq!(cert.key_id, "host.example.com");
+    assert_eq!(cert.valid_principals, vec!["host.example.com", "10.0.0.1"]);
+    assert_eq!(cert.valid_after, 1577836800);
+    assert_eq!(cert.valid_before, 1893456000);
+    assert!(cert.critical_options.is_empty());
+    assert!(cert.extensions.is_empty());
+    assert_eq!(cert.signature_key, ca);
+}
+
+#[test]
+fn test_cert_builder_random_nonce() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let build = || {
+        sshkeys::CertificateBuilder::new(key.clone(), sshkeys::CertType::User, key.clone())
+            .build()
+            .unwrap()
+    };
+
+    assert_ne!(build(), build());
+}
+
+#[test]
+fn test_sk_certs() {
+    let certs = vec![
+        (
+            "id_ed25519_sk",
+            "sk-ssh-ed25519-cert-v01@openssh.com",
+            "ED25519-SK-CERT",
+            sshkeys::KeyTypeKind::SkEd25519Cert,
+        ),
+        (
+            "id_ecdsa_sk",
+            "sk-ecdsa-sha2-nistp256-cert-v01@openssh.com",
+            "ECDSA-SK-CERT",
+            sshkeys::KeyTypeKind::SkEcdsaCert,
+        ),
+    ];
+
+    for (name, kt_name, short_name, kind) in certs {
+        let key = sshkeys::PublicKey::from_path(format!("tests/test-keys/{}.pub", name)).unwrap();
+        let path = format!("tests/test-keys/{}-cert.pub", name);
+        let cert = sshkeys::Certificate::from_path(&path).unwrap();
+
+        assert_eq!(cert.key_type.name, kt_name);
+        assert_eq!(cert.key_type.plain, key.key_type.name);
+        assert_eq!(cert.key_type.short_name, short_name);
+        assert!(cert.key_type.is_cert);
+        assert_eq!(cert.key_type.kind, kind);
+        assert_eq!(cert.key.kind, key.kind);
+        assert_eq!(cert.key_id, "sk-test");
+        assert_eq!(cert.valid_principals, vec!["me"]);
+
+        // The builder encodes the same certificate for the security key
+        let mut builder = sshkeys::CertificateBuilder::new(
+            key,
+            sshkeys::CertType::User,
+            cert.signature_key.clone(),
+        )
+        .nonce(cert.nonce.clone())
+        .key_id(&cert.key_id)
+        .principal("me")
+        .valid_after(cert.valid_after)
+        .valid_before(cert.valid_before);
+
+        for name in cert.extensions.keys() {
+            builder = builder.extension(name);
+        }
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let data = base64::decode(contents.split_whitespace().nth(1).unwrap()).unwrap();
+        assert_eq!(
+            builder.build().unwrap(),
+            &data[..data.len() - 4 - cert.signature.len()]
+        );
+    }
+}
+
+#[test]
+fn test_cert_builder_unsupported_key_type() {
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    let curve = sshkeys::Curve::from_identifier("secp256k1").unwrap();
+    let mut point = vec![4];
+    point.extend_from_slice(&[0x5c; 64]);
+
+    let keys = vec![
+        sshkeys::PublicKey::from_path("tests/test-keys/id_ed448.pub").unwrap(),
+        sshkeys::PublicKey::from_path("tests/test-keys/id_xmss.pub").unwrap(),
+        sshkeys::PublicKey::from_ecdsa(curve, point),
+        // The subject key of a certificate has the key type of the certificate
+        cert.key.clone(),
+    ];
+
+    for key in keys {
+        let name = key.key_type.name;
+        let result = sshkeys::CertificateBuilder::new(
+            key,
+            sshkeys::CertType::User,
+            cert.signature_key.clone(),
+        )
+        .nonce(vec![0; 32])
+        .build();
+
+        match result {
+            Ok(v) => panic!("Expected unsupported key type, got {:?}", v),
+            Err(e) => assert_eq!(e.to_string(), format!("Unsupported key type {}", name)),
+        }
+    }
+}
+
+#[test]
+fn test_fingerprint_with_config() {
+    let cert =
+        sshkeys::Certificate::from_path("tests/test-keys/id_ed25519_ecdsa_ca-cert.pub").unwrap();
+    let data = cert.signature_key.encode();
+
+    let fp = sshkeys::Fingerprint::compute(sshkeys::FingerprintKind::Sha256, &data);
+    assert_eq!(fp.hash(), "GWNZ/+mMkNGKigKk78KR8Ip1PUg2uqBYfp1G/fGHcCQ");
+
+    let fp = sshkeys::Fingerprint::compute_with_config(
+        sshkeys::FingerprintKind::Sha256,
+        &data,
+        base64::URL_SAFE_NO_PAD,
+    );
+    assert_eq!(fp.kind, sshkeys::FingerprintKind::Sha256);
+    assert_eq!(fp.hash(), "GWNZ_-mMkNGKigKk78KR8Ip1PUg2uqBYfp1G_fGHcCQ");
+
+    let fp = sshkeys::Fingerprint::compute_with_config(
+        sshkeys::FingerprintKind::Sha256,
+        &data,
+        base64::STANDARD,
+    );
+    assert_eq!(fp.hash(), "GWNZ/+mMkNGKigKk78KR8Ip1PUg2uqBYfp1G/fGHcCQ=");
+
+    // The configuration does not apply to MD5 fingerprints
+    let fp = sshkeys::Fingerprint::compute_with_config(
+        sshkeys::FingerprintKind::Md5,
+        &data,
+        base64::URL_SAFE_NO_PAD,
+    );
+    assert_eq!(
+        fp.hash(),
+        sshkeys::Fingerprint::compute(sshkeys::FingerprintKind::Md5, &data).hash()
+    );
+}
+
+#[test]
+fn test_pubkey_debug() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    assert_eq!(
+        format!("{:?}", key),
+        "PublicKey { key_type: \"ssh-rsa\", bits: 2048, fingerprint: \"SHA256:5mDozobgKuNO6/FutOgATBvGfYQbNfBlUY6iBYSdqF0\", comment: Some(\"me@home\") }"
+    );
+
+    // The raw key data is still available through the key kind
+    let kind = format!("{:?}", key.kind);
+    assert!(kind.starts_with("Rsa(RsaPublicKey {"));
+}
+
+#[test]
+fn test_pubkey_validate() {
+    for path in &[
+        "tests/test-keys/id_rsa_2048.pub",
+        "tests/test-keys/id_dsa_1024.pub",
+        "tests/test-keys/id_ecdsa_256.pub",
+        "tests/test-keys/id_ed25519.pub",
+        "tests/test-keys/id_ed25519_sk.pub",
+        "tests/test-keys/id_ecdsa_sk.pub",
+        "tests/test-keys/id_ed448.pub",
+    ] {
+        let key = sshkeys::PublicKey::from_path(path).unwrap();
+        assert!(key.validate().is_ok(), "{} failed validation", path);
+    }
+
+    let weak_key = |key: sshkeys::PublicKey| match key.validate() {
+        Ok(_) => panic!("Expected weak key"),
+        Err(e) => e.to_string(),
+    };
+
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_rsa(vec![1, 0, 1], vec![0; 256])),
+        "Weak key: RSA modulus of 0 bits is smaller than 1024 bits"
+    );
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_rsa(vec![1, 0, 1], vec![1])),
+        "Weak key: RSA modulus of 1 bits is smaller than 1024 bits"
+    );
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_dsa_1024.pub").unwrap();
+    let dsa = match key.kind {
+        sshkeys::PublicKeyKind::Dsa(ref k) => k.clone(),
+        _ => panic!("Expected DSA public key"),
+    };
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_dsa(
+            vec![0],
+            dsa.q.clone(),
+            dsa.g.clone(),
+            dsa.y.clone()
+        )),
+        "Weak key: DSA primes are too small"
+    );
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_dsa(
+            dsa.p.clone(),
+            dsa.q.clone(),
+            vec![1],
+            dsa.y.clone()
+        )),
+        "Weak key: DSA parameters are out of range"
+    );
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_dsa(
+            dsa.p.clone(),
+            dsa.q.clone(),
+            dsa.g.clone(),
+            dsa.p.clone()
+        )),
+        "Weak key: DSA parameters are out of range"
+    );
+
+    let curve = sshkeys::Curve::from_identifier("nistp256").unwrap();
+    let mut point = vec![0; 65];
+    point[0] = 0x04;
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_ecdsa(curve, point)),
+        "Weak key: ECDSA point is zero"
+    );
+
+    // The identity and a point of order 8
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_ed25519(vec![0; 32])),
+        "Weak key: ED25519 point has small order"
+    );
+    let mut identity = vec![0; 32];
+    identity[0] = 1;
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_ed25519(identity)),
+        "Weak key: ED25519 point has small order"
+    );
+    let order8 = base64::decode("JuiVj8KyJ7BFw/SJ8u+Y8NXfrAXTxjM5sTgCiG1T/AU=").unwrap();
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_ed25519(order8)),
+        "Weak key: ED25519 point has small order"
+    );
+
+    assert_eq!(
+        weak_key(sshkeys::PublicKey::from_ed448(vec![0; 57])),
+        "Weak key: ED448 key is zero"
+    );
+}
+
+#[test]
+fn test_pubkey_encoded_len() {
+    for entry in fs::read_dir("tests/test-keys").unwrap() {
+        let path = entry.unwrap().path();
+        let key = match sshkeys::PublicKey::from_path(&path) {
+            Ok(k) => k,
+            Err(_) => continue,
+        };
+
+        assert_eq!(key.encoded_len(), key.encode().len(), "{}", path.display());
+    }
+
+    // Leading zeros are not encoded and a zero byte is prepended if the high bit is set
+    for n in &[vec![0, 0, 0x7f], vec![0x80, 1], vec![0, 0, 0], vec![]] {
+        let key = sshkeys::PublicKey::from_rsa(vec![0, 1, 0, 1], n.clone());
+        assert_eq!(key.encoded_len(), key.encode().len());
+    }
+}
+
+#[test]
+fn test_pubkey_try_from() {
+    let line =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home";
+    let key = sshkeys::PublicKey::try_from(line).unwrap();
+    assert_eq!(key.key_type.name, "ssh-ed25519");
+    assert_eq!(key.comment, Some("me@home".to_string()));
+
+    let data = key.encode();
+    let other = sshkeys::PublicKey::try_from(data.as_slice()).unwrap();
+    assert_eq!(other, key);
+    assert_eq!(other.comment, None);
+
+    match sshkeys::PublicKey::try_from("ssh-ed25519") {
+        Ok(v) => panic!("Expected invalid format, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Invalid format"),
+    }
+}
+
+#[test]
+fn test_pubkey_from_str() {
+    let contents = fs::read_to_string("tests/test-keys/id_ecdsa_256.pub").unwrap();
+    let key: sshkeys::PublicKey = contents.parse().unwrap();
+    assert_eq!(
+        key,
+        sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_256.pub").unwrap()
+    );
+    assert_eq!(key.comment, Some("me@home".to_string()));
+
+    assert!("ssh-foo AAAA".parse::<sshkeys::PublicKey>().is_err());
+}
+
+#[test]
+fn test_sk_pubkey_round_trip() {
+    for name in &["id_ed25519_sk.pub", "id_ecdsa_sk.pub"] {
+        let contents = fs::read_to_string(format!("tests/test-keys/{}", name)).unwrap();
+        let data = base64::decode(contents.split_whitespace().nth(1).unwrap()).unwrap();
+
+        let key = sshkeys::PublicKey::from_bytes(&data).unwrap();
+        assert_eq!(key.encode(), data);
+        assert_eq!(key.encoded_len(), data.len());
+
+        let key = sshkeys::PublicKey::from_string(&contents).unwrap();
+        assert_eq!(key.to_string(), contents.trim_end());
+    }
+
+    // Resident keys may use an application string other than the default `ssh:`
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519_sk.pub").unwrap();
+    let sk = match key.kind {
+        sshkeys::PublicKeyKind::SkEd25519(ref k) => k,
+        _ => panic!("Expected ED25519 security key public key"),
+    };
+
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("sk-ssh-ed25519@openssh.com");
+    writer.write_bytes(&sk.key);
+    writer.write_string("ssh:resident");
+    let data = writer.into_bytes();
+
+    let resident = sshkeys::PublicKey::from_bytes(&data).unwrap();
+    match resident.kind {
+        sshkeys::PublicKeyKind::SkEd25519(ref k) => assert_eq!(k.application, "ssh:resident"),
+        _ => panic!("Expected ED25519 security key public key"),
+    }
+    assert_eq!(resident.encode(), data);
+}
+
+#[test]
+fn test_reader_peek_bytes() {
+    let data = vec![0, 0, 0, 3, 1, 2, 3, 42];
+    let mut reader = sshkeys::Reader::new(&data);
+
+    assert_eq!(reader.peek_bytes(0).unwrap(), [0u8; 0]);
+    assert_eq!(reader.peek_bytes(4).unwrap(), [0, 0, 0, 3]);
+    assert_eq!(reader.peek_bytes(8).unwrap(), &data[..]);
+    assert_eq!(reader.position(), 0);
+
+    assert_eq!(reader.read_bytes().unwrap(), [1, 2, 3]);
+    assert_eq!(reader.peek_bytes(1).unwrap(), [42]);
+    assert_eq!(reader.position(), 7);
+
+    match reader.peek_bytes(2) {
+        Ok(v) => panic!("Expected unexpected EOF, got {:?}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Unexpected EOF reached while reading 2 bytes at offset 7"
+        ),
+    }
+    assert_eq!(reader.position(), 7);
+}
+
+#[test]
+fn test_reader_set_position() {
+    let data = vec![0, 0, 0, 1, 42, 0, 0, 0, 7];
+    let mut reader = sshkeys::Reader::new(&data);
+
+    reader.set_position(5).unwrap();
+    assert_eq!(reader.read_u32().unwrap(), 7);
+    reader.set_position(0).unwrap();
+    assert_eq!(reader.read_bytes().unwrap(), [42]);
+
+    // Seeking to the end is allowed, but not past it
+    reader.set_position(data.len()).unwrap();
+    assert_eq!(reader.remaining(), 0);
+    match reader.set_position(data.len() + 3) {
+        Ok(v) => panic!("Expected unexpected EOF, got {:?}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Unexpected EOF reached while reading 3 bytes at offset 9"
+        ),
+    }
+    assert_eq!(reader.position(), data.len());
+}
+
+#[test]
+fn test_reader_with_checkpoint() {
+    let data = vec![0, 0, 0, 2, 0xff, 0xfe, 0, 0, 0, 42];
+    let mut reader = sshkeys::Reader::new(&data);
+
+    // A failing closure rewinds the reader
+    assert!(reader.with_checkpoint(|r| r.read_string()).is_err());
+    assert_eq!(reader.position(), 0);
+
+    assert_eq!(
+        reader.with_checkpoint(|r| r.read_bytes()).unwrap(),
+        [0xff, 0xfe]
+    );
+    assert_eq!(reader.position(), 6);
+
+    // The reader is rewound to the checkpoint, even after several successful reads
+    let result = reader.with_checkpoint(|r| {
+        r.read_u32()?;
+        r.read_u32()
+    });
+    assert!(result.is_err());
+    assert_eq!(reader.position(), 6);
+    assert_eq!(reader.read_u32().unwrap(), 42);
+}
+
+#[test]
+fn test_pubkey_fingerprints() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let (sha256fp, sha384fp, sha512fp) = key.fingerprints();
+
+    for (fp, kind) in [
+        (sha256fp, sshkeys::FingerprintKind::Sha256),
+        (sha384fp, sshkeys::FingerprintKind::Sha384),
+        (sha512fp, sshkeys::FingerprintKind::Sha512),
+    ] {
+        let expected = key.fingerprint_with(kind);
+        assert_eq!(fp.kind, expected.kind);
+        assert_eq!(fp.hash(), expected.hash());
+    }
+}
+
+#[test]
+fn test_pubkey_to_key_string_wrapped() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let data = base64::encode(key.encode());
+
+    for width in &[64, 76] {
+        let wrapped = key.to_key_string_wrapped(*width);
+        let lines: Vec<&str> = wrapped.lines().collect();
+        assert!(lines.len() > 1);
+
+        assert!(lines[0].starts_with("ssh-rsa "));
+        assert_eq!(lines[0].len(), "ssh-rsa ".len() + width);
+        assert!(lines[lines.len() - 1].ends_with(" me@home"));
+        for line in &lines[1..lines.len() - 1] {
+            assert_eq!(line.len(), *width);
+        }
+
+        let unwrapped: String = wrapped
+            .trim_start_matches("ssh-rsa ")
+            .trim_end_matches(" me@home")
+            .lines()
+            .collect();
+        assert_eq!(unwrapped, data);
+    }
+
+    assert_eq!(key.to_key_string_wrapped(0), key.to_key_string());
+    assert_eq!(key.to_key_string_wrapped(data.len()), key.to_key_string());
+}
+
+#[test]
+fn test_ecdsa_pubkey_coordinates() {
+    for &(name, len) in &[
+        ("id_ecdsa_256.pub", 32),
+        ("id_ecdsa_384.pub", 48),
+        ("id_ecdsa_521.pub", 66),
+    ] {
+        let key = sshkeys::PublicKey::from_path(format!("tests/test-keys/{}", name)).unwrap();
+        let ecdsa = match key.kind {
+            sshkeys::PublicKeyKind::Ecdsa(ref k) => k,
+            _ => panic!("Expected ECDSA public key"),
+        };
+
+        let point = ecdsa.point();
+        assert_eq!(point, &ecdsa.key[..]);
+        assert_eq!(point[0], 0x04);
+
+        let (x, y) = ecdsa.coordinates().unwrap();
+        assert_eq!(x.len(), len);
+        assert_eq!(y.len(), len);
+        assert_eq!(x, &point[1..1 + len]);
+        assert_eq!(y, &point[1 + len..]);
+    }
+
+    let compressed = sshkeys::EcdsaPublicKey {
+        curve: sshkeys::Curve::from_identifier("nistp256").unwrap(),
+        key: vec![0x02; 33],
+    };
+    match compressed.coordinates() {
+        Ok(v) => panic!("Expected invalid point, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Invalid elliptic curve point"),
+    }
+}
+
+#[test]
+fn test_cert_key_id_and_serial() {
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    assert_eq!(cert.key_id, "john.doe");
+    assert_eq!(cert.serial, 0);
+
+    let display = cert.to_string();
+    assert!(display.contains("        Key ID: \"john.doe\"\n"));
+    assert!(display.contains("        Serial: 0\n"));
+}
+
+#[test]
+fn test_cert_display() {
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    let expected = "        Type: ssh-ed25519-cert-v01@openssh.com user certificate
+        Public key: ED25519-CERT SHA256:ppYFPx0k4Ogs230n6eX9vGPpnNsTB0LPrDWXh1YjClA
+        Signing CA: RSA SHA256:8bEmsdiV2BXhjrzPhp8dPrSLUK3U/YpIXT8NIw6Ym+s (using ssh-rsa)
+        Key ID: \"john.doe\"
+        Serial: 0
+        Valid: from 2017-10-02T08:49:00 to 2018-10-01T08:50:41
+        Principals: \n                root
+        Critical Options: \n                force-command /usr/bin/true
+        Extensions: \n                permit-X11-forwarding
+                permit-agent-forwarding
+                permit-port-forwarding
+                permit-pty
+                permit-user-rc";
+    assert_eq!(cert.to_string(), expected);
+
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519_host-cert.pub").unwrap();
+    let expected = "        Type: ssh-ed25519-cert-v01@openssh.com host certificate
+        Public key: ED25519-CERT SHA256:kkaqMnJz4XAhwz7n7Ov8RbHEYIJ8sxyGQWDmM5Ckot0
+        Signing CA: ED25519 SHA256:elYqUIgEUqMyc8AdNNk+IeI+2l1vWEh4K4n03hqhoD8 (using ssh-ed25519)
+        Key ID: \"host01\"
+        Serial: 0
+        Valid: from 2017-10-02T09:20:00 to 2018-10-01T09:21:56
+        Principals: \n                host01.example.com
+        Critical Options: (none)
+        Extensions: (none)";
+    assert_eq!(cert.to_string(), expected);
+}
+
+#[test]
+fn test_cert_display_validity() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let valid = |after: u64, before: u64| {
+        let mut data =
+            sshkeys::CertificateBuilder::new(key.clone(), sshkeys::CertType::User, key.clone())
+                .valid_after(after)
+                .valid_before(before)
+                .build()
+                .unwrap();
+        let mut writer = sshkeys::Writer::new();
+        writer.write_nested(|w| {
+            w.write_string("ssh-ed25519");
+            w.write_bytes(&[0; 64]);
+        });
+        data.extend_from_slice(&writer.into_bytes());
+
+        let cert = sshkeys::Certificate::from_bytes(&data).unwrap();
+        let display = cert.to_string();
+        assert!(display.contains("        Principals: (none)\n"));
+
+        display
+            .lines()
+            .find(|l| l.starts_with("        Valid: "))
+            .unwrap()
+            .trim_start_matches("        Valid: ")
+            .to_string()
+    };
+
+    assert_eq!(valid(0, u64::MAX), "forever");
+    assert_eq!(valid(0, 951782400), "before 2000-02-29T00:00:00");
+    assert_eq!(valid(1577836799, u64::MAX), "after 2019-12-31T23:59:59");
+    assert_eq!(
+        valid(1, 4102444800),
+        "from 1970-01-01T00:00:01 to 2100-01-01T00:00:00"
+    );
+}
+
+#[test]
+fn test_cert_type() {
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    assert_eq!(cert.cert_type(), sshkeys::CertType::User);
+
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519_host-cert.pub").unwrap();
+    assert_eq!(cert.cert_type(), sshkeys::CertType::Host);
+}
+
+#[test]
+#[should_panic(expected = "Invalid certificate type with value 3")]
+fn test_cert_unknown_type() {
+    let mut data = encode_cert_with_options(&[]);
+
+    // The certificate type follows the key type, nonce, key and serial
+    let offset = 4 + "ssh-ed25519-cert-v01@openssh.com".len() + 4 + 32 + 4 + 32 + 8;
+    assert_eq!(&data[offset..offset + 4], [0, 0, 0, 1]);
+    data[offset + 3] = 3;
+
+    match sshkeys::Certificate::from_bytes(&data) {
+        Ok(v) => panic!("Expected invalid certificate type, got {:?}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+fn test_writer_reader_u8_round_trip() {
+    let mut writer = sshkeys::Writer::new();
+    writer.write_u8(0x01);
+    writer.write_u32(42);
+    writer.write_u8(0xff);
+    let bytes = writer.into_bytes();
+    assert_eq!(bytes, [0x01, 0, 0, 0, 42, 0xff]);
+
+    let mut reader = sshkeys::Reader::new(&bytes);
+    assert_eq!(reader.read_u8().unwrap(), 0x01);
+    assert_eq!(reader.read_u32().unwrap(), 42);
+    assert_eq!(reader.read_u8().unwrap(), 0xff);
+    assert_eq!(reader.remaining(), 0);
+
+    match reader.read_u8() {
+        Ok(v) => panic!("Expected unexpected EOF, got {}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Unexpected EOF reached while reading 1 bytes at offset 6"
+        ),
+    }
+    assert_eq!(reader.position(), 6);
+}
+
+#[test]
+fn test_writer_reader_bool_round_trip() {
+    let mut writer = sshkeys::Writer::new();
+    writer.write_bool(true);
+    writer.write_bool(false);
+    let bytes = writer.into_bytes();
+    assert_eq!(bytes, [1, 0]);
+
+    let mut reader = sshkeys::Reader::new(&bytes);
+    assert!(reader.read_bool().unwrap());
+    assert!(!reader.read_bool().unwrap());
+    assert!(reader.read_bool().is_err());
+
+    // Any non-zero value is true
+    let mut reader = sshkeys::Reader::new(&[0x80, 0xff]);
+    assert!(reader.read_bool().unwrap());
+    assert!(reader.read_bool().unwrap());
+}
+
+#[test]
+fn test_pubkey_comment_modification() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let encoded = key.encode();
+
+    let mut key = key.with_comment(Some("admin@example.com".to_string()));
+    assert_eq!(key.comment, Some("admin@example.com".to_string()));
+    assert!(key.to_key_string().ends_with(" admin@example.com"));
+    assert_eq!(key.encode(), encoded);
+
+    key.set_comment(None);
+    assert_eq!(key.comment, None);
+    assert_eq!(
+        key.to_key_string(),
+        format!("ssh-rsa {}", base64::encode(&encoded))
+    );
+}
+
+#[test]
+fn test_key_type_from_name_whitespace() {
+    for name in ["ssh-rsa\r", " ssh-rsa", "ssh-rsa\r\n", "\tssh-rsa "] {
+        let kt = sshkeys::KeyType::from_name(name).unwrap();
+        assert_eq!(kt.name, "ssh-rsa");
+        assert_eq!(kt.kind, sshkeys::KeyTypeKind::Rsa);
+    }
+
+    // The case of the name must match
+    for name in ["SSH-RSA", "Ssh-Rsa\r"] {
+        match sshkeys::KeyType::from_name(name) {
+            Ok(v) => panic!("Expected UnknownKeyType, got {:?}", v),
+            Err(e) => assert_eq!(e.to_string(), format!("Unknown key type {}", name.trim())),
+        }
+    }
+}
+
+#[test]
+fn test_key_line_type_case_and_whitespace() {
+    let contents = fs::read_to_string("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let expected = sshkeys::PublicKey::from_string(&contents).unwrap();
+
+    for name in ["SSH-RSA", "Ssh-Rsa"] {
+        let line = contents.replacen("ssh-rsa", name, 1);
+        let key = sshkeys::PublicKey::from_string(&line).unwrap();
+        assert_eq!(key, expected);
+        assert_eq!(key.key_type.name, "ssh-rsa");
+
+        let kt = sshkeys::PublicKey::peek_key_type(&line).unwrap();
+        assert_eq!(kt.kind, sshkeys::KeyTypeKind::Rsa);
+    }
+
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    let line = contents.replacen(
+        "ssh-ed25519-cert-v01@openssh.com",
+        "SSH-ED25519-CERT-V01@OPENSSH.COM",
+        1,
+    );
+    let cert = sshkeys::Certificate::from_string(&line).unwrap();
+    assert_eq!(cert.key_type.name, "ssh-ed25519-cert-v01@openssh.com");
+
+    match sshkeys::PublicKey::from_string("ssh-foo AAAA\r") {
+        Ok(v) => panic!("Expected UnknownKeyType, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Unknown key type ssh-foo"),
+    }
+}
+
+#[test]
+fn test_key_blob_type_is_exact() {
+    // The key type names in key blobs are compared exactly, the same way as OpenSSH does
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let mut blob = key.encode();
+    blob[4..15].copy_from_slice(b"SSH-ED25519");
+
+    match sshkeys::PublicKey::from_bytes(&blob) {
+        Ok(v) => panic!("Expected UnknownKeyType, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Unknown key type SSH-ED25519"),
+    }
+
+    // Surrounding whitespace is not ignored either
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ssh-ed25519\r");
+    writer.write_bytes(&[0; 32]);
+
+    match sshkeys::PublicKey::from_bytes(&writer.into_bytes()) {
+        Ok(v) => panic!("Expected UnknownKeyType, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Unknown key type ssh-ed25519\r"),
+    }
+
+    let line = format!("ssh-ed25519 {}", base64::encode(&blob));
+    match sshkeys::PublicKey::from_string(&line) {
+        Ok(v) => panic!("Expected key type mismatch, got {:?}", v),
+        Err(e) => assert_eq!(e.to_string(), "Key type mismatch"),
+    }
+}
+
+#[test]
+fn test_pubkey_from_string_crlf() {
+    let contents =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd\r\n";
+    let key = sshkeys::PublicKey::from_string(contents).unwrap();
+    assert_eq!(key.key_type.name, "ssh-ed25519");
+    assert_eq!(key.comment, None);
+
+    let contents = "SSH-ED25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home\r\n";
+    let key = sshkeys::PublicKey::from_string(contents).unwrap();
+    assert_eq!(key.key_type.name, "ssh-ed25519");
+    assert_eq!(key.comment, Some("me@home".to_string()));
+    assert_eq!(
+        key.to_key_string(),
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd me@home"
+    );
+}
+
+#[test]
+fn test_pubkey_from_string_empty_input() {
+    for contents in [
+        "",
+        "   ",
+        "\r\n",
+        "\n\n",
+        "# a comment",
+        "  # ssh-rsa AAAA\n",
+        "# a comment\n\n# another comment\n",
+    ] {
+        match sshkeys::PublicKey::from_string(contents) {
+            Ok(v) => panic!("Expected EmptyInput, got {:?}", v),
+            Err(e) => {
+                assert!(e.is_empty_input());
+                assert_eq!(e.to_string(), "Input is empty or contains only a comment");
+            }
+        }
+    }
+
+    // Malformed lines are not reported as empty input
+    for contents in ["ssh-rsa", "ssh-foo AAAA", "ssh-ed25519 !!!"] {
+        let e = sshkeys::PublicKey::from_string(contents).unwrap_err();
+        assert!(!e.is_empty_input());
+    }
+
+    let e = sshkeys::Certificate::from_string("# a comment").unwrap_err();
+    assert!(e.is_empty_input());
+
+    // A key following a comment is not silently ignored
+    let contents = "# a comment\nssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHkbe7gwx7s0dlApEEzpUyOAPrzPLy4czEZw/sh8m8rd";
+    match sshkeys::PublicKey::from_string(contents) {
+        Ok(v) => panic!("Expected invalid format, got {:?}", v),
+        Err(e) => {
+            assert!(!e.is_empty_input());
+            assert_eq!(e.to_string(), "Invalid format");
+        }
+    }
+}
+
+#[test]
+fn test_pubkey_key_data() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let data = key.key_data();
+    assert_eq!(data.len(), 256);
+    match key.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => assert!(k.n.ends_with(&data)),
+        _ => panic!("Expected RSA public key"),
+    }
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_dsa_1024.pub").unwrap();
+    let data = key.key_data();
+    assert_ne!(data[0], 0);
+    match key.kind {
+        sshkeys::PublicKeyKind::Dsa(ref k) => assert!(k.y.ends_with(&data)),
+        _ => panic!("Expected DSA public key"),
+    }
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ecdsa_384.pub").unwrap();
+    let data = key.key_data();
+    assert_eq!(data.len(), 97);
+    assert_eq!(data[0], 0x04);
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519_sk.pub").unwrap();
+    assert_eq!(key.key_data().len(), 32);
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed448.pub").unwrap();
+    assert_eq!(key.key_data().len(), 57);
+}
+
+#[test]
+fn test_pubkey_from_ppk() {
+    let expected = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519_v2.ppk").unwrap();
+    let key = sshkeys::PublicKey::from_ppk(&contents).unwrap();
+    assert_eq!(key, expected);
+    assert_eq!(key.comment, Some("me@home".to_string()));
+
+    let expected = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048_v3.ppk").unwrap();
+    assert_eq!(key, expected);
+    assert_eq!(key.key_type.name, "ssh-rsa");
+    assert_eq!(key.bits(), 2048);
+    assert_eq!(key.comment, Some("rsa-key-20201010".to_string()));
+}
+
+#[test]
+fn test_pubkey_from_ppk_invalid() {
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519_v2.ppk").unwrap();
+
+    let cases = [
+        // Unsupported version of the format
+        contents.replace("PuTTY-User-Key-File-2", "PuTTY-User-Key-File-1"),
+        // Key type of the header does not match the key
+        contents.replace("File-2: ssh-ed25519", "File-2: ssh-rsa"),
+        // Missing public key lines
+        contents.replace("Public-Lines: 2", "Public-Lines: x"),
+        contents.replace("Public-Lines: 2", "Public-Lines: 9"),
+        contents.replace("Public-Lines: 2", "Public-Key: 2"),
+    ];
+    let errors = [
+        "Invalid format",
+        "Key type mismatch",
+        "Invalid format",
+        "Invalid format",
+        "Invalid format",
+    ];
+
+    for (contents, error) in cases.iter().zip(errors.iter()) {
+        match sshkeys::PublicKey::from_ppk(contents) {
+            Ok(v) => panic!("Expected error, got {:?}", v),
+            Err(e) => assert_eq!(e.to_string(), *error),
+        }
+    }
+}
+
+#[test]
+fn test_pubkey_to_putty_public() {
+    let contents = fs::read_to_string("tests/test-keys/id_rsa_2048_putty.pub").unwrap();
+    let key = sshkeys::PublicKey::from_string(&contents).unwrap();
+    assert_eq!(
+        key,
+        sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap()
+    );
+    assert_eq!(key.comment, Some("rsa-key-20201010".to_string()));
+    assert_eq!(key.to_putty_public(), contents);
+
+    // The public key of a PuTTY key file is exported the same way
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048_v3.ppk").unwrap();
+    assert_eq!(key.to_putty_public(), contents);
+
+    // The comment header is present even if the key has no comment
+    let key = key.with_comment(None);
+    let exported = key.to_putty_public();
+    assert!(exported.contains("\nComment: \"\"\n"));
+    let imported = sshkeys::PublicKey::from_string(&exported).unwrap();
+    assert_eq!(imported, key);
+    assert_eq!(imported.comment, None);
+}
+
+#[test]
+fn test_curve_new() {
+    let kinds = [
+        (sshkeys::CurveKind::Nistp256, "nistp256"),
+        (sshkeys::CurveKind::Nistp384, "nistp384"),
+        (sshkeys::CurveKind::Nistp521, "nistp521"),
+        (sshkeys::CurveKind::Secp256k1, "secp256k1"),
+    ];
+
+    for (kind, identifier) in kinds.iter() {
+        let curve = sshkeys::Curve::new(kind.clone());
+        assert_eq!(curve.kind, *kind);
+        assert_eq!(curve.identifier, *identifier);
+        assert_eq!(curve, sshkeys::Curve::from_identifier(identifier).unwrap());
+    }
+}
+
+#[test]
+fn test_pubkey_bubblebabble() {
+    // Generated with `ssh-keygen -B -f <path>`
+    let keys = [
+        (
+            "tests/test-keys/id_rsa_2048.pub",
+            "xesiz-loral-tatyz-kevif-ryvob-datic-megut-lunif-zideg-popyh-nyxux",
+        ),
+        (
+            "tests/test-keys/id_dsa_1024.pub",
+            "xudec-pubep-hekun-tepam-cylet-vegan-cyhyr-sapim-hesys-delum-huxox",
+        ),
+        (
+            "tests/test-keys/id_ecdsa_521.pub",
+            "xukek-mytin-cyket-ticun-gemog-lykel-gusab-rolyt-pukaf-tobus-taxyx",
+        ),
+        (
+            "tests/test-keys/id_ed25519.pub",
+            "xosab-mukyv-hygem-cekog-coviv-vepek-kityf-puzyd-tunyg-vynib-fixux",
+        ),
+        (
+            "tests/test-keys/id_ed25519_sk.pub",
+            "xecer-selum-senis-tomyz-lemaz-fydyb-tinyf-vutuv-vavas-cobuz-texyx",
+        ),
+        (
+            "tests/test-keys/id_ecdsa_sk.pub",
+            "xocek-bovek-zisov-takop-zedat-makod-rurov-saryv-tilaf-zosyr-luxox",
+        ),
+    ];
+
+    for (path, expected) in keys.iter() {
+        let key = sshkeys::PublicKey::from_path(path).unwrap();
+        assert_eq!(key.bubblebabble(), *expected);
+    }
+}
+
+#[test]
+fn test_pubkey_encode_presized() {
+    // The writer used for encoding is sized up front, so the
+    // encoded key never needs to grow its buffer.
+    for entry in fs::read_dir("tests/test-keys").unwrap() {
+        let path = entry.unwrap().path();
+        let key = match sshkeys::PublicKey::from_path(&path) {
+            Ok(k) => k,
+            Err(_) => continue,
+        };
+
+        let encoded = key.encode();
+        assert_eq!(encoded.capacity(), encoded.len(), "{}", path.display());
+    }
+}
+
+#[test]
+fn test_writer_with_capacity() {
+    let mut writer = sshkeys::Writer::with_capacity(17);
+    writer.write_string("a test string");
+    let bytes = writer.into_bytes();
+    assert_eq!(bytes.len(), 17);
+    assert_eq!(bytes.capacity(), 17);
+
+    let mut writer = sshkeys::Writer::with_capacity(0);
+    writer.write_u32(42);
+    assert_eq!(writer.into_bytes(), [0, 0, 0, 42]);
+}
+
+#[test]
+fn test_reader_borrowed_values() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let encoded = key.encode();
+
+    // The fields of the key can be inspected without copying them
+    let mut reader = sshkeys::Reader::new(&encoded);
+    let kt_name = reader.read_str().unwrap();
+    let e = reader.read_mpint_ref().unwrap();
+    let n = reader.read_mpint_ref().unwrap();
+    assert_eq!(reader.remaining(), 0);
+
+    assert_eq!(kt_name, "ssh-rsa");
+    assert_eq!(e, [1, 0, 1]);
+    assert_eq!(n.len() * 8, key.bits());
+    match key.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => assert_eq!(n, &k.n[..]),
+        _ => panic!("Expected RSA public key"),
+    }
+
+    // The borrowed values outlive the reader
+    let blob = {
+        let mut reader = sshkeys::Reader::new(&encoded);
+        reader.read_bytes_ref().unwrap()
+    };
+    assert_eq!(blob, b"ssh-rsa");
+
+    let mut reader = sshkeys::Reader::new(&[0, 0, 0, 2, 0xc3, 0x28]);
+    match reader.read_str() {
+        Ok(v) => panic!("Expected UTF-8 error, got {:?}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            sshkeys::Reader::new(&[0, 0, 0, 2, 0xc3, 0x28])
+                .read_string()
+                .unwrap_err()
+                .to_string()
+        ),
+    }
+
+    let mut reader = sshkeys::Reader::new(&[0, 0, 0, 5, 1]);
+    assert!(reader.read_bytes_ref().is_err());
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn test_pubkey_bits_significant() {
+    // Generated with `ssh-keygen -t rsa -b 1025`, for which `ssh-keygen -l` reports 1025 bits
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_1025.pub").unwrap();
+    assert_eq!(key.bits(), 1025);
+
+    // Leading zero bytes and bits of the modulus are not counted
+    let mut n = vec![0, 0, 0x80];
+    n.extend_from_slice(&[0xff; 255]);
+    let key = sshkeys::PublicKey::from_rsa(vec![1, 0, 1], n);
+    assert_eq!(key.bits(), 2048);
+
+    let mut n = vec![0x7f];
+    n.extend_from_slice(&[0xff; 255]);
+    let key = sshkeys::PublicKey::from_rsa(vec![1, 0, 1], n);
+    assert_eq!(key.bits(), 2047);
+
+    let mut p = vec![0, 0x01];
+    p.extend_from_slice(&[0xff; 128]);
+    let key = sshkeys::PublicKey::from_dsa(p, vec![1; 20], vec![2], vec![3]);
+    assert_eq!(key.bits(), 1025);
+}
+
+#[test]
+fn test_pubkey_original_blob() {
+    // The exponent and modulus have redundant leading zero bytes
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ssh-rsa");
+    writer.write_bytes(&[0, 1, 0, 1]);
+    writer.write_bytes(&[0, 0, 0xc5, 0x1b]);
+    let blob = writer.into_bytes();
+    let line = format!("ssh-rsa {} me@home", base64::encode(&blob));
+
+    // The key blob is only kept when asked for
+    let key = sshkeys::PublicKey::from_string(&line).unwrap();
+    assert_eq!(key.original_blob(), None);
+    assert_ne!(key.to_key_string(), line);
+    assert_eq!(
+        sshkeys::PublicKey::from_bytes(&blob)
+            .unwrap()
+            .original_blob(),
+        None
+    );
+
+    let mut key = sshkeys::PublicKey::from_string_preserving(&line).unwrap();
+    assert_eq!(key.original_blob(), Some(&blob[..]));
+    assert_ne!(key.encode(), blob);
+    assert_eq!(key.to_key_string(), line);
+    assert_eq!(key.to_string(), line);
+    let mut written = Vec::new();
+    key.write(&mut written).unwrap();
+    assert_eq!(written, format!("{}\n", line).into_bytes());
+
+    // Changing the comment keeps the original key blob
+    key.comment = Some("me@work".to_string());
+    assert_eq!(key.original_blob(), Some(&blob[..]));
+    assert_eq!(key.to_key_string(), line.replace("me@home", "me@work"));
+
+    // Modified keys fall back to the encoded form
+    key.kind = sshkeys::PublicKey::from_rsa(vec![3], vec![0xc5, 0x1b]).kind;
+    assert_eq!(key.original_blob(), None);
+    assert_eq!(
+        key.to_key_string(),
+        "ssh-rsa AAAAB3NzaC1yc2EAAAABAwAAAAMAxRs= me@work"
+    );
+
+    // The multi-line formats keep the key blob as well
+    let rfc4716 = format!(
+        "{}\n{}\n{}\n",
+        "---- BEGIN SSH2 PUBLIC KEY ----",
+        base64::encode(&blob),
+        "---- END SSH2 PUBLIC KEY ----"
+    );
+    let key = sshkeys::PublicKey::from_string_preserving(&rfc4716).unwrap();
+    assert_eq!(key.original_blob(), Some(&blob[..]));
+    assert_eq!(
+        sshkeys::PublicKey::from_string(&rfc4716)
+            .unwrap()
+            .original_blob(),
+        None
+    );
+
+    // Certificates do not keep the key blobs of their keys
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    assert_eq!(cert.key.original_blob(), None);
+    assert_eq!(cert.signature_key.original_blob(), None);
+
+    // Canonical keys are re-emitted the same way in both cases
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519.pub").unwrap();
+    let key = sshkeys::PublicKey::from_string_preserving(&contents).unwrap();
+    assert_eq!(key.original_blob(), Some(&key.encode()[..]));
+    assert_eq!(key.to_key_string(), contents.trim_end());
+}
+
+#[test]
+fn test_pubkey_canonical_encode() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let rsa = match key.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => k.clone(),
+        _ => panic!("Expected RSA public key"),
+    };
+
+    // The same key, with redundant leading zero bytes in the blob and the values
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ssh-rsa");
+    writer.write_bytes(&[&[0, 0][..], &rsa.e].concat());
+    writer.write_bytes(&[&[0, 0, 0][..], &rsa.n].concat());
+    let parsed = sshkeys::PublicKey::from_bytes(&writer.into_bytes()).unwrap();
+
+    let mut n = vec![0; 4];
+    n.extend_from_slice(&rsa.n);
+    let constructed = sshkeys::PublicKey::new(
+        sshkeys::KeyType::from_name("ssh-rsa").unwrap(),
+        sshkeys::PublicKeyKind::Rsa(sshkeys::RsaPublicKey {
+            e: rsa.e.clone(),
+            n,
+        }),
+        None,
+    );
+
+    for other in &[parsed, constructed] {
+        assert_eq!(other.canonical_encode(), other.encode());
+        assert_eq!(other.canonical_encode(), key.canonical_encode());
+        assert_eq!(other.fingerprint().hash(), key.fingerprint().hash());
+        assert_eq!(
+            other.fingerprint_with(sshkeys::FingerprintKind::Md5).hash(),
+            key.fingerprint_with(sshkeys::FingerprintKind::Md5).hash()
+        );
+    }
+
+    let contents = fs::read_to_string("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let blob = base64::decode(contents.split_whitespace().nth(1).unwrap()).unwrap();
+    assert_eq!(key.canonical_encode(), blob);
+}
+
+#[test]
+fn test_pubkey_is_deprecated() {
+    let deprecated = [
+        (
+            "tests/test-keys/id_dsa_1024.pub",
+            "DSA keys are deprecated and disabled by default since OpenSSH 7.0",
+        ),
+        (
+            "tests/test-keys/id_rsa_1024.pub",
+            "RSA key of 1024 bits is smaller than the recommended 2048 bits",
+        ),
+    ];
+
+    for (path, note) in deprecated.iter() {
+        let key = sshkeys::PublicKey::from_path(path).unwrap();
+        assert!(key.is_deprecated(), "{}", path);
+        assert_eq!(key.security_notes(), vec![note.to_string()]);
+    }
+
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_dsa_1024-cert.pub").unwrap();
+    assert!(cert.key.is_deprecated());
+
+    let current = [
+        "tests/test-keys/id_rsa_2048.pub",
+        "tests/test-keys/id_ecdsa_256.pub",
+        "tests/test-keys/id_ed25519.pub",
+        "tests/test-keys/id_ed25519_sk.pub",
+    ];
+
+    for path in current.iter() {
+        let key = sshkeys::PublicKey::from_path(path).unwrap();
+        assert!(!key.is_deprecated(), "{}", path);
+        assert!(key.security_notes().is_empty());
+    }
+}
+
+#[test]
+fn test_pubkey_rsa_max_bits() {
+    let blob = |n: &[u8]| {
+        let mut writer = sshkeys::Writer::new();
+        writer.write_string("ssh-rsa");
+        writer.write_mpint(&[1, 0, 1]);
+        writer.write_mpint(n);
+        writer.into_bytes()
+    };
+
+    assert_eq!(sshkeys::RSA_MAX_BITS, 16384);
+
+    let max = vec![0xff; sshkeys::RSA_MAX_BITS / 8];
+    let key = sshkeys::PublicKey::from_bytes(&blob(&max)).unwrap();
+    assert_eq!(key.bits(), sshkeys::RSA_MAX_BITS);
+
+    let mut too_large = vec![0x01];
+    too_large.extend_from_slice(&max);
+    match sshkeys::PublicKey::from_bytes(&blob(&too_large)) {
+        Ok(v) => panic!("Expected too large key, got {:?}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Key of 16385 bits exceeds the maximum supported size of 16384 bits"
+        ),
+    }
+
+    // A hostile modulus of one megabyte is rejected as well
+    let line = format!("ssh-rsa {}", base64::encode(blob(&vec![0x42; 1 << 20])));
+    match sshkeys::PublicKey::from_string(&line) {
+        Ok(v) => panic!("Expected too large key, got {:?}", v),
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Key of 8388607 bits exceeds the maximum supported size of 16384 bits"
+        ),
+    }
+}
+
+#[test]
+fn test_multi_word_comments() {
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519.pub").unwrap();
+    let data = contents.split_whitespace().nth(1).unwrap();
+
+    // The whole remainder of the line is the comment, including inner whitespace
+    let line = format!("ssh-ed25519 {}  email@host my  laptop\t(work) \n", data);
+    let key = sshkeys::PublicKey::from_string(&line).unwrap();
+    assert_eq!(
+        key.comment,
+        Some("email@host my  laptop\t(work)".to_string())
+    );
+    assert_eq!(
+        key.to_key_string(),
+        format!("ssh-ed25519 {} email@host my  laptop\t(work)", data)
+    );
+
+    let entry = sshkeys::AuthorizedKey::from_string(&format!("no-pty {}", line)).unwrap();
+    assert_eq!(
+        entry.comment,
+        Some("email@host my  laptop\t(work)".to_string())
+    );
+
+    let contents = fs::read_to_string("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    let line = format!("{} my laptop", contents.trim_end());
+    let cert = sshkeys::Certificate::from_string(&line).unwrap();
+    assert_eq!(cert.comment, Some("me@home my laptop".to_string()));
+}
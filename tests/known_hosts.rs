@@ -0,0 +1,111 @@
+use std::fs::File;
+
+extern crate sshkeys;
+
+#[test]
+fn test_parse_known_hosts() {
+    let file = File::open("tests/test-keys/known_hosts").unwrap();
+    let entries = sshkeys::parse_known_hosts(file).unwrap();
+
+    assert_eq!(entries.len(), 5);
+
+    let ed25519 = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    assert_eq!(entries[0].marker, None);
+    assert_eq!(
+        entries[0].hosts,
+        sshkeys::HostPatterns::Patterns(vec!["example.com".to_string(), "10.0.0.1".to_string()])
+    );
+    assert!(entries[0].key.matches(&ed25519));
+    assert_eq!(entries[0].key.comment, None);
+
+    assert_eq!(entries[1].marker, None);
+    assert_eq!(
+        entries[1].hosts,
+        sshkeys::HostPatterns::Hashed(
+            "|1|O0Gsr/W9UYZJhCtbicAyDLPHT5Q=|9Dz+4BobzWqy2gE3k8LfZ96nU1g=".to_string()
+        )
+    );
+    assert!(entries[1].key.matches(&ed25519));
+
+    let ca = sshkeys::PublicKey::from_path("tests/test-keys/ssh_ca_host_key.pub").unwrap();
+    assert_eq!(
+        entries[2].marker,
+        Some(sshkeys::KnownHostMarker::CertAuthority)
+    );
+    assert_eq!(
+        entries[2].hosts,
+        sshkeys::HostPatterns::Patterns(vec!["*.example.com".to_string()])
+    );
+    assert!(entries[2].key.matches(&ca));
+
+    assert_eq!(entries[3].marker, Some(sshkeys::KnownHostMarker::Revoked));
+    assert_eq!(entries[3].key.key_type.kind, sshkeys::KeyTypeKind::Rsa);
+
+    assert_eq!(entries[4].marker, None);
+    assert_eq!(
+        entries[4].hosts,
+        sshkeys::HostPatterns::Patterns(vec![
+            "[example.org]:2222".to_string(),
+            "*.internal".to_string(),
+            "!bad.internal".to_string()
+        ])
+    );
+    assert_eq!(entries[4].key.key_type.kind, sshkeys::KeyTypeKind::Ecdsa);
+    assert_eq!(entries[4].key.comment, Some("me@home".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Invalid format")]
+fn test_known_host_unknown_marker() {
+    let line = "@trusted example.com ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIMIVp6q5co/r5GwY0dH+NYQbfKicapeF3gXEU3dzaAvD";
+
+    match sshkeys::KnownHost::from_string(line) {
+        Ok(v) => panic!("Expected invalid format, got {:?}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+fn test_known_host_matches_host() {
+    let file = File::open("tests/test-keys/known_hosts").unwrap();
+    let entries = sshkeys::parse_known_hosts(file).unwrap();
+
+    assert!(entries[0].matches_host("example.com"));
+    assert!(entries[0].matches_host("EXAMPLE.com"));
+    assert!(entries[0].matches_host("10.0.0.1"));
+    assert!(!entries[0].matches_host("www.example.com"));
+
+    // Hashed entry of `host.example.com`
+    assert!(entries[1].matches_host("host.example.com"));
+    assert!(!entries[1].matches_host("other.example.com"));
+
+    assert!(entries[2].matches_host("www.example.com"));
+    assert!(entries[2].matches_host("a.b.example.com"));
+    assert!(!entries[2].matches_host("example.com"));
+
+    assert!(entries[4].matches_host("[example.org]:2222"));
+    assert!(!entries[4].matches_host("example.org"));
+    assert!(entries[4].matches_host("good.internal"));
+    assert!(!entries[4].matches_host("bad.internal"));
+}
+
+#[test]
+fn test_known_host_matches_wildcards() {
+    let key = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIMIVp6q5co/r5GwY0dH+NYQbfKicapeF3gXEU3dzaAvD";
+    let entry =
+        sshkeys::KnownHost::from_string(&format!("web?.example.com,db*.internal {}", key)).unwrap();
+
+    assert!(entry.matches_host("web1.example.com"));
+    assert!(!entry.matches_host("web.example.com"));
+    assert!(!entry.matches_host("web12.example.com"));
+    assert!(entry.matches_host("db.internal"));
+    assert!(entry.matches_host("db-primary.internal"));
+    assert!(!entry.matches_host("db.internal.example.com"));
+
+    let entry = sshkeys::KnownHost::from_string(&format!("* {}", key)).unwrap();
+    assert!(entry.matches_host("anything"));
+
+    let entry = sshkeys::KnownHost::from_string(&format!("!*.example.com,* {}", key)).unwrap();
+    assert!(entry.matches_host("example.org"));
+    assert!(!entry.matches_host("www.example.com"));
+}
@@ -18,6 +18,14 @@ fn serde_ok_both_ways() {
     );
 }
 
+#[test]
+fn serde_ok_both_ways_without_comment() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_1024.pub").unwrap();
+    assert_eq!(key.comment, None);
+
+    assert_tokens(&key, &[Token::String("ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAAAgQC+TbX3SNqSrKOTVp2PxWA1sm//M6NQhl/wLZgwlUfitJyc5HMkyLF8ClrNc97XPs8M+yHTn7X3NVBK/SMO9J7vAPugGND+pIjYVFor3Ft++/WT+t5XcCS/MTzo6D5m6CZ/IuBYqTtELkrJjf/0YbV1X9Vwzmt8P2xiFrl5Gq2B6w==")]);
+}
+
 #[test]
 fn serde_de_error() {
     struct MockError {}
@@ -29,5 +37,12 @@ fn serde_de_error() {
     let err_format = MockError {}.to_string();
 
     assert_de_tokens_error::<sshkeys::PublicKey>(&[Token::Str("M")], &err_format);
-    assert_de_tokens_error::<sshkeys::PublicKey>(&[Token::Str("")], &err_format);
+    assert_de_tokens_error::<sshkeys::PublicKey>(
+        &[Token::Str("")],
+        "Input is empty or contains only a comment",
+    );
+    assert_de_tokens_error::<sshkeys::PublicKey>(
+        &[Token::Str("ssh-foo AAAA")],
+        "Unknown key type ssh-foo",
+    );
 }
@@ -0,0 +1,109 @@
+extern crate sshkeys;
+
+#[test]
+fn test_rsa_signature_from_cert() {
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_rsa_2048-cert.pub").unwrap();
+    let signature = sshkeys::Signature::from_bytes(&cert.signature).unwrap();
+
+    assert_eq!(signature.algorithm, "ssh-rsa");
+    match signature.kind {
+        sshkeys::SignatureKind::Rsa(ref v) => assert_eq!(v.len(), 256),
+        _ => panic!("Expected RSA signature"),
+    }
+}
+
+#[test]
+fn test_rsa_sha2_signature_from_cert() {
+    let names = vec![
+        ("id_ed25519_rsa_sha256_ca-cert.pub", "rsa-sha2-256"),
+        ("id_ed25519_rsa_sha512_ca-cert.pub", "rsa-sha2-512"),
+    ];
+
+    for (name, algorithm) in names {
+        let cert = sshkeys::Certificate::from_path(format!("tests/test-keys/{}", name)).unwrap();
+        assert_eq!(cert.signature_key.key_type.name, "ssh-rsa");
+
+        let signature = sshkeys::Signature::from_bytes(&cert.signature).unwrap();
+        assert_eq!(signature.algorithm, algorithm);
+        match signature.kind {
+            sshkeys::SignatureKind::Rsa(ref v) => assert!(!v.is_empty(), "{}", name),
+            _ => panic!("Expected RSA signature"),
+        }
+    }
+}
+
+#[test]
+fn test_ed25519_signature_from_cert() {
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519_host-cert.pub").unwrap();
+    let signature = sshkeys::Signature::from_bytes(&cert.signature).unwrap();
+
+    assert_eq!(signature.algorithm, "ssh-ed25519");
+    match signature.kind {
+        sshkeys::SignatureKind::Ed25519(ref v) => assert_eq!(v.len(), 64),
+        _ => panic!("Expected ED25519 signature"),
+    }
+}
+
+#[test]
+fn test_ecdsa_signature_from_cert() {
+    let cert =
+        sshkeys::Certificate::from_path("tests/test-keys/id_ed25519_ecdsa_ca-cert.pub").unwrap();
+    assert_eq!(cert.signature_key.key_type.name, "ecdsa-sha2-nistp384");
+
+    let signature = sshkeys::Signature::from_bytes(&cert.signature).unwrap();
+    assert_eq!(signature.algorithm, "ecdsa-sha2-nistp384");
+    match signature.kind {
+        sshkeys::SignatureKind::Ecdsa(ref sig) => {
+            assert!(!sig.r.is_empty() && sig.r.len() <= 48);
+            assert!(!sig.s.is_empty() && sig.s.len() <= 48);
+        }
+        _ => panic!("Expected ECDSA signature"),
+    }
+}
+
+#[test]
+fn test_ecdsa_signature_leading_zero() {
+    let mut inner = sshkeys::Writer::new();
+    inner.write_mpint(&[0x80, 1]);
+    inner.write_mpint(&[0x7f, 2]);
+
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ecdsa-sha2-nistp256");
+    writer.write_bytes(&inner.into_bytes());
+
+    let signature = sshkeys::Signature::from_bytes(&writer.into_bytes()).unwrap();
+    assert_eq!(
+        signature.kind,
+        sshkeys::SignatureKind::Ecdsa(sshkeys::EcdsaSignature {
+            r: vec![0x80, 1],
+            s: vec![0x7f, 2],
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unknown signature type ssh-foo")]
+fn test_unknown_signature_type() {
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ssh-foo");
+    writer.write_bytes(&[0; 64]);
+
+    match sshkeys::Signature::from_bytes(&writer.into_bytes()) {
+        Ok(v) => panic!("Expected unknown signature type, got {:?}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Trailing data after key")]
+fn test_signature_trailing_data() {
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ssh-ed25519");
+    writer.write_bytes(&[0; 64]);
+    writer.write_u32(0);
+
+    match sshkeys::Signature::from_bytes(&writer.into_bytes()) {
+        Ok(v) => panic!("Expected trailing data, got {:?}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "spki")]
+
+use std::fs;
+
+extern crate sshkeys;
+
+#[test]
+fn test_from_spki_der() {
+    // Generated with `ssh-keygen -e -m PKCS8` and `openssl pkey -pubin -outform DER`
+    let keys = [
+        "id_rsa_2048",
+        "id_dsa_1024",
+        "id_ecdsa_256",
+        "id_ecdsa_384",
+        "id_ecdsa_521",
+        "id_ed25519",
+    ];
+
+    for name in keys.iter() {
+        let der = fs::read(format!("tests/test-keys/{}_spki.der", name)).unwrap();
+        let expected =
+            sshkeys::PublicKey::from_path(format!("tests/test-keys/{}.pub", name)).unwrap();
+
+        let key = sshkeys::PublicKey::from_spki_der(&der).unwrap();
+        assert_eq!(key, expected, "{}", name);
+        assert_eq!(key.key_type.name, expected.key_type.name);
+        assert_eq!(key.comment, None);
+    }
+}
+
+#[test]
+fn test_from_spki_der_invalid() {
+    let der = fs::read("tests/test-keys/id_ed25519_spki.der").unwrap();
+
+    let error = |der: &[u8]| match sshkeys::PublicKey::from_spki_der(der) {
+        Ok(v) => panic!("Expected error, got {:?}", v),
+        Err(e) => e.to_string(),
+    };
+
+    assert_eq!(error(&[]), "Invalid format");
+    assert_eq!(error(&der[..der.len() - 1]), "Invalid format");
+
+    let mut trailing = der.clone();
+    trailing.push(0);
+    assert_eq!(error(&trailing), "Trailing data after key");
+
+    // Unknown algorithm, 1.3.101.110 (X25519)
+    let mut unknown = der.clone();
+    unknown[8] = 0x6e;
+    assert_eq!(error(&unknown), "Unknown key type 1.3.101.110");
+
+    // Unused bits in the bit string
+    let mut unused = der.clone();
+    unused[11] = 1;
+    assert_eq!(error(&unused), "Invalid format");
+
+    // Unknown curve, 1.2.840.10045.3.1.1 (P-192)
+    let mut curve = fs::read("tests/test-keys/id_ecdsa_256_spki.der").unwrap();
+    assert_eq!(curve[21..23], [0x01, 0x07]);
+    curve[22] = 0x01;
+    assert_eq!(error(&curve), "Unknown curve 1.2.840.10045.3.1.1");
+
+    // Compressed points are not supported
+    let mut compressed = fs::read("tests/test-keys/id_ecdsa_256_spki.der").unwrap();
+    compressed[26] = 0x02;
+    assert_eq!(error(&compressed), "Invalid elliptic curve point");
+}
+
+#[test]
+fn test_to_spki_der() {
+    // The encoding must be the same as the one produced by OpenSSL
+    let keys = [
+        "id_rsa_2048",
+        "id_dsa_1024",
+        "id_ecdsa_256",
+        "id_ecdsa_384",
+        "id_ecdsa_521",
+        "id_ed25519",
+    ];
+
+    for name in keys.iter() {
+        let expected = fs::read(format!("tests/test-keys/{}_spki.der", name)).unwrap();
+        let key = sshkeys::PublicKey::from_path(format!("tests/test-keys/{}.pub", name)).unwrap();
+
+        assert_eq!(key.to_spki_der().unwrap(), expected, "{}", name);
+    }
+
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed448.pub").unwrap();
+    let der = key.to_spki_der().unwrap();
+    assert_eq!(sshkeys::PublicKey::from_spki_der(&der).unwrap(), key);
+
+    // Leading zero bytes are not encoded, and values with the high bit set are prefixed by one
+    let key = sshkeys::PublicKey::from_rsa(vec![0, 1, 0, 1], vec![0x80, 0, 0, 0x01]);
+    assert_eq!(
+        key.to_spki_der().unwrap(),
+        [
+            0x30, 0x20, 0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01,
+            0x01, 0x05, 0x00, 0x03, 0x0f, 0x00, 0x30, 0x0c, 0x02, 0x05, 0x00, 0x80, 0x00, 0x00,
+            0x01, 0x02, 0x03, 0x01, 0x00, 0x01,
+        ][..]
+    );
+}
+
+#[test]
+fn test_to_spki_der_security_keys() {
+    for path in &[
+        "tests/test-keys/id_ed25519_sk.pub",
+        "tests/test-keys/id_ecdsa_sk.pub",
+    ] {
+        let key = sshkeys::PublicKey::from_path(path).unwrap();
+        match key.to_spki_der() {
+            Ok(v) => panic!("Expected unsupported key type, got {:?}", v),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                format!("Unsupported key type {}", key.key_type.name)
+            ),
+        }
+    }
+}
@@ -0,0 +1,298 @@
+#![cfg(feature = "verify")]
+
+use std::fs;
+
+extern crate base64;
+extern crate sshkeys;
+
+fn from_hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+// Test vectors from RFC 8032, section 7.1
+const TEST_VECTORS: &[(&str, &str, &str)] = &[
+    (
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        "",
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    ),
+    (
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        "72",
+        "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+    ),
+    (
+        "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+        "af82",
+        "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+    ),
+];
+
+#[test]
+fn test_ed25519_verify_rfc8032() {
+    for &(key, message, signature) in TEST_VECTORS {
+        let key = sshkeys::PublicKey::from_ed25519(from_hex(key));
+        let k = match key.kind {
+            sshkeys::PublicKeyKind::Ed25519(ref k) => k,
+            _ => panic!("Expected ED25519 public key"),
+        };
+
+        let message = from_hex(message);
+        let mut signature = from_hex(signature);
+        assert!(k.verify(&message, &signature).unwrap());
+
+        // A modified message or signature must not verify
+        let mut modified = message.clone();
+        modified.push(0);
+        assert!(!k.verify(&modified, &signature).unwrap());
+
+        signature[0] ^= 1;
+        assert!(!k.verify(&message, &signature).unwrap());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Invalid format")]
+fn test_ed25519_verify_invalid_signature_length() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let k = match key.kind {
+        sshkeys::PublicKeyKind::Ed25519(ref k) => k,
+        _ => panic!("Expected ED25519 public key"),
+    };
+
+    match k.verify(b"message", &[0; 63]) {
+        Ok(v) => panic!("Expected invalid format, got {}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+// Returns the to-be-signed part of a certificate, its CA key and its signature.
+fn read_signed_cert(path: &str) -> (Vec<u8>, sshkeys::PublicKey, sshkeys::Signature) {
+    let contents = fs::read_to_string(path).unwrap();
+    let data = base64::decode(contents.split_whitespace().nth(1).unwrap()).unwrap();
+    let cert = sshkeys::Certificate::from_bytes(&data).unwrap();
+
+    let tbs = data[..data.len() - 4 - cert.signature.len()].to_vec();
+    let signature = sshkeys::Signature::from_bytes(&cert.signature).unwrap();
+
+    (tbs, cert.signature_key, signature)
+}
+
+#[test]
+fn test_rsa_verify_openssh_certificates() {
+    let certs = vec![
+        ("id_ed25519-cert.pub", "ssh-rsa", sshkeys::RsaHash::Sha1),
+        (
+            "id_ed25519_rsa_sha256_ca-cert.pub",
+            "rsa-sha2-256",
+            sshkeys::RsaHash::Sha256,
+        ),
+        (
+            "id_ed25519_rsa_sha512_ca-cert.pub",
+            "rsa-sha2-512",
+            sshkeys::RsaHash::Sha512,
+        ),
+    ];
+
+    for (name, algorithm, hash) in certs {
+        let (tbs, ca, signature) = read_signed_cert(&format!("tests/test-keys/{}", name));
+        assert_eq!(signature.algorithm, algorithm);
+
+        let k = match ca.kind {
+            sshkeys::PublicKeyKind::Rsa(ref k) => k,
+            _ => panic!("Expected RSA public key"),
+        };
+        let mut blob = match signature.kind {
+            sshkeys::SignatureKind::Rsa(v) => v,
+            _ => panic!("Expected RSA signature"),
+        };
+
+        assert!(k.verify(&tbs, &blob, hash).unwrap(), "{}", name);
+
+        // A different hash algorithm, message or signature must not verify
+        let other = match hash {
+            sshkeys::RsaHash::Sha1 => sshkeys::RsaHash::Sha256,
+            _ => sshkeys::RsaHash::Sha1,
+        };
+        assert!(!k.verify(&tbs, &blob, other).unwrap());
+        assert!(!k.verify(&tbs[1..], &blob, hash).unwrap());
+
+        blob[0] ^= 1;
+        assert!(!k.verify(&tbs, &blob, hash).unwrap());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Invalid format")]
+fn test_rsa_verify_invalid_key() {
+    let k = sshkeys::RsaPublicKey {
+        e: vec![1, 0, 1],
+        n: vec![],
+    };
+
+    match k.verify(b"message", &[0; 256], sshkeys::RsaHash::Sha256) {
+        Ok(v) => panic!("Expected invalid format, got {}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+fn test_ecdsa_verify_openssh_certificates() {
+    let certs = vec![
+        ("id_ed25519_ecdsa_p256_ca-cert.pub", "ecdsa-sha2-nistp256"),
+        ("id_ed25519_ecdsa_ca-cert.pub", "ecdsa-sha2-nistp384"),
+        ("id_ed25519_ecdsa_p521_ca-cert.pub", "ecdsa-sha2-nistp521"),
+    ];
+
+    for (name, algorithm) in certs {
+        let (tbs, ca, signature) = read_signed_cert(&format!("tests/test-keys/{}", name));
+        assert_eq!(signature.algorithm, algorithm);
+
+        let k = match ca.kind {
+            sshkeys::PublicKeyKind::Ecdsa(ref k) => k,
+            _ => panic!("Expected ECDSA public key"),
+        };
+        let sig = match signature.kind {
+            sshkeys::SignatureKind::Ecdsa(ref s) => s,
+            _ => panic!("Expected ECDSA signature"),
+        };
+
+        let encode = |r: &[u8], s: &[u8]| {
+            let mut writer = sshkeys::Writer::new();
+            writer.write_mpint(r);
+            writer.write_mpint(s);
+            writer.into_bytes()
+        };
+
+        assert!(k.verify(&tbs, &encode(&sig.r, &sig.s)).unwrap(), "{}", name);
+
+        // A modified message or signature must not verify
+        assert!(!k.verify(&tbs[1..], &encode(&sig.r, &sig.s)).unwrap());
+        assert!(!k.verify(&tbs, &encode(&sig.s, &sig.r)).unwrap());
+        assert!(!k.verify(&tbs, &encode(&[], &sig.s)).unwrap());
+
+        let mut r = sig.r.clone();
+        r.insert(0, 1);
+        assert!(!k.verify(&tbs, &encode(&r, &sig.s)).unwrap());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Invalid elliptic curve point")]
+fn test_ecdsa_verify_invalid_point() {
+    let k = sshkeys::EcdsaPublicKey {
+        curve: sshkeys::Curve::from_identifier("nistp256").unwrap(),
+        key: vec![0x04; 65],
+    };
+
+    let mut writer = sshkeys::Writer::new();
+    writer.write_mpint(&[1]);
+    writer.write_mpint(&[1]);
+
+    match k.verify(b"message", &writer.into_bytes()) {
+        Ok(v) => panic!("Expected invalid point, got {}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+fn test_dsa_verify_openssh_certificate() {
+    // Signed with `ssh-keygen -s id_dsa_1024`
+    let (tbs, ca, signature) = read_signed_cert("tests/test-keys/id_ed25519_dsa_ca-cert.pub");
+    assert_eq!(signature.algorithm, "ssh-dss");
+
+    let k = match ca.kind {
+        sshkeys::PublicKeyKind::Dsa(ref k) => k,
+        _ => panic!("Expected DSA public key"),
+    };
+    let mut blob = match signature.kind {
+        sshkeys::SignatureKind::Dsa(v) => v,
+        _ => panic!("Expected DSA signature"),
+    };
+
+    assert!(k.verify(&tbs, &blob).unwrap());
+
+    // A modified message or signature must not verify
+    assert!(!k.verify(&tbs[1..], &blob).unwrap());
+    assert!(!k.verify(&tbs, &[0; 40]).unwrap());
+
+    blob[39] ^= 1;
+    assert!(!k.verify(&tbs, &blob).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Invalid format")]
+fn test_dsa_verify_invalid_signature_length() {
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_dsa_1024.pub").unwrap();
+    let k = match key.kind {
+        sshkeys::PublicKeyKind::Dsa(ref k) => k,
+        _ => panic!("Expected DSA public key"),
+    };
+
+    match k.verify(b"message", &[1; 39]) {
+        Ok(v) => panic!("Expected invalid signature length, got {}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
+
+#[test]
+fn test_cert_verify() {
+    let names = vec![
+        "id_rsa_2048-cert.pub",
+        "id_dsa_1024-cert.pub",
+        "id_ecdsa_521-cert.pub",
+        "id_ed25519-cert.pub",
+        "id_ed25519_host-cert.pub",
+        "id_ed25519_rsa_sha256_ca-cert.pub",
+        "id_ed25519_rsa_sha512_ca-cert.pub",
+        "id_ed25519_ecdsa_p256_ca-cert.pub",
+        "id_ed25519_ecdsa_ca-cert.pub",
+        "id_ed25519_ecdsa_p521_ca-cert.pub",
+        "id_ed25519_dsa_ca-cert.pub",
+        "id_ed25519_sk-cert.pub",
+        "id_ecdsa_sk-cert.pub",
+    ];
+
+    for name in names {
+        let path = format!("tests/test-keys/{}", name);
+        let mut cert = sshkeys::Certificate::from_path(&path).unwrap();
+        assert!(cert.verify().unwrap(), "{}", name);
+
+        // Only the bytes as they were read are verified, not the fields
+        cert.serial += 1;
+        cert.valid_principals.push("admin".to_string());
+        assert!(cert.verify().unwrap(), "{}", name);
+
+        // Flipping a bit of the nonce invalidates the signature
+        let contents = fs::read_to_string(&path).unwrap();
+        let mut data = base64::decode(contents.split_whitespace().nth(1).unwrap()).unwrap();
+        let offset = 4 + cert.key_type.name.len() + 4;
+        data[offset] ^= 1;
+        let cert = sshkeys::Certificate::from_bytes(&data).unwrap();
+        assert!(!cert.verify().unwrap(), "{}", name);
+    }
+}
+
+#[test]
+fn test_cert_verify_non_canonical_encoding() {
+    // The subject key is encoded with redundant leading zero bytes, so
+    // re-encoding the parsed certificate would not give the signed bytes
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_rsa_2048_noncanonical-cert.pub")
+        .unwrap();
+    assert!(cert.verify().unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Key type mismatch")]
+fn test_cert_verify_mismatched_signature() {
+    let mut cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    cert.signature_key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+
+    match cert.verify() {
+        Ok(v) => panic!("Expected key type mismatch, got {}", v),
+        Err(e) => panic!("{}", e.to_string()),
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "zeroize")]
+
+extern crate sshkeys;
+extern crate zeroize;
+
+use zeroize::{Zeroize, Zeroizing};
+
+#[test]
+fn test_pubkey_zeroize() {
+    let mut key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    key.zeroize();
+
+    match key.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => {
+            assert!(k.e.is_empty());
+            assert!(k.n.is_empty());
+        }
+        _ => panic!("Expected RSA public key"),
+    }
+    assert_eq!(key.comment, None);
+}
+
+#[test]
+fn test_pubkey_move_fields() {
+    // The feature must not prevent moving the fields out of a key
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_rsa_2048.pub").unwrap();
+    let k = match key.kind {
+        sshkeys::PublicKeyKind::Rsa(k) => k,
+        _ => panic!("Expected RSA public key"),
+    };
+
+    let n = k.n;
+    assert_eq!(n.len(), 256);
+}
+
+#[test]
+fn test_cert_zeroize() {
+    let mut cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    cert.zeroize();
+
+    assert!(cert.nonce.is_empty());
+    assert!(cert.key_id.is_empty());
+    assert!(cert.valid_principals.is_empty());
+    assert!(cert.critical_options.is_empty());
+    assert!(cert.extensions.is_empty());
+    assert!(cert.signature.is_empty());
+    match cert.signature_key.kind {
+        sshkeys::PublicKeyKind::Rsa(ref k) => assert!(k.n.is_empty()),
+        _ => panic!("Expected RSA public key"),
+    }
+}
+
+#[test]
+fn test_zeroizing_wrappers() {
+    // Wrapping keys and certificates in `Zeroizing` clears them when they are dropped
+    let key = sshkeys::PublicKey::from_path("tests/test-keys/id_ed25519.pub").unwrap();
+    let key = Zeroizing::new(key);
+    assert_eq!(key.bits(), 256);
+
+    let cert = sshkeys::Certificate::from_path("tests/test-keys/id_ed25519-cert.pub").unwrap();
+    let mut cert = Zeroizing::new(cert);
+    assert!(!cert.extensions.is_empty());
+
+    cert.zeroize();
+    assert!(cert.extensions.is_empty());
+}
+
+#[test]
+fn test_signature_zeroize() {
+    let mut writer = sshkeys::Writer::new();
+    writer.write_string("ssh-ed25519");
+    writer.write_bytes(&[0x2a; 64]);
+
+    let mut signature = sshkeys::Signature::from_bytes(&writer.into_bytes()).unwrap();
+    signature.zeroize();
+    assert_eq!(signature.kind, sshkeys::SignatureKind::Ed25519(vec![]));
+}